@@ -1,4 +1,4 @@
-use elevenlabs_stt::{ElevenLabsSTTClient, STTResponse, models};
+use elevenlabs_stt::{ElevenLabsSTTClient, STTResponse, TimestampsGranularity, models};
 use std::env;
 
 #[tokio::main]
@@ -20,7 +20,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .model(models::elevanlabs_models::SCRIBE_V1)
         .language_code("en")
         .tag_audio_events(true)
-        .timestamps_granularity("word")
+        .timestamps_granularity(TimestampsGranularity::Word)
         .diarize(true)
         .diarization_threshold(0.22)
         .webhook(false)
@@ -0,0 +1,189 @@
+//! Forced alignment: time a known transcript against its audio.
+
+use crate::ElevenLabsSTTClient;
+use crate::error::ElevenLabsSTTError;
+use crate::types::{STTResponse, STTResponseWord, STTResponseWordCharacters};
+use crate::upload::{UploadControl, UploadSource};
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+/// Word timing returned by the forced alignment endpoint
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ForcedAlignmentWord {
+    pub text: String,
+    pub start: f32,
+    pub end: f32,
+    /// How poorly the audio matched this word, lower is better
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loss: Option<f32>,
+}
+
+/// Forced alignment result
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ForcedAlignmentResponse {
+    #[serde(default)]
+    pub characters: Vec<STTResponseWordCharacters>,
+    #[serde(default)]
+    pub words: Vec<ForcedAlignmentWord>,
+    /// Average loss over all words
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loss: Option<f32>,
+}
+
+impl ForcedAlignmentResponse {
+    /// Convert the alignment into a transcript with word timestamps,
+    /// so it can be used wherever an [`STTResponse`] is expected (e.g. subtitle export).
+    pub fn to_stt_response(&self) -> STTResponse {
+        let mut words: Vec<STTResponseWord> = Vec::with_capacity(self.words.len() * 2);
+
+        for word in &self.words {
+            if let Some(previous_end) = words.last().and_then(|previous| previous.end) {
+                words.push(STTResponseWord {
+                    text: Some(" ".to_string()),
+                    start: Some(previous_end),
+                    end: Some(word.start),
+                    type_field: Some("spacing".to_string()),
+                    ..STTResponseWord::default()
+                });
+            }
+            words.push(STTResponseWord {
+                text: Some(word.text.clone()),
+                start: Some(word.start),
+                end: Some(word.end),
+                type_field: Some("word".to_string()),
+                ..STTResponseWord::default()
+            });
+        }
+
+        let text = words
+            .iter()
+            .filter_map(|word| word.text.as_deref())
+            .collect::<String>();
+
+        STTResponse {
+            text: Some(text),
+            words: Some(words),
+            ..STTResponse::default()
+        }
+    }
+}
+
+/// Builder for a forced alignment request
+pub struct ForcedAlignmentBuilder {
+    client: ElevenLabsSTTClient,
+    audio: UploadSource,
+    text: String,
+    enabled_spooled_file: Option<bool>,
+    control: UploadControl,
+}
+
+impl ForcedAlignmentBuilder {
+    pub(crate) fn new(client: ElevenLabsSTTClient, audio: UploadSource, text: String) -> Self {
+        Self {
+            client,
+            audio,
+            text,
+            enabled_spooled_file: None,
+            control: UploadControl::default(),
+        }
+    }
+
+    /// Stream the file to the model in chunks, for very large files (optional)
+    pub fn enabled_spooled_file(mut self, enabled: bool) -> Self {
+        self.enabled_spooled_file = Some(enabled);
+        self
+    }
+
+    /// Abort the request (including pending retries) when `token` is cancelled
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.control.cancellation = Some(token);
+        self
+    }
+
+    /// Check the request locally without sending it
+    pub fn validate(&self) -> Result<(), ElevenLabsSTTError> {
+        if self.text.trim().is_empty() {
+            return Err(ElevenLabsSTTError::ValidationError(
+                "text: must not be empty".to_string(),
+            ));
+        }
+
+        if let Some(header) = self.audio.header()? {
+            crate::validation::validate_media(&header)?;
+        }
+
+        let size = self.audio.len()?;
+        if size >= crate::models::MAX_FILE_SIZE {
+            return Err(ElevenLabsSTTError::ValidationError(format!(
+                "file: {} bytes exceeds the {} byte limit",
+                size,
+                crate::models::MAX_FILE_SIZE
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Execute the forced alignment request and return word and character timings
+    pub async fn execute(self) -> Result<ForcedAlignmentResponse, ElevenLabsSTTError> {
+        self.validate()?;
+
+        let text = &self.text;
+        let enabled_spooled_file = self.enabled_spooled_file;
+
+        self.client
+            .send_multipart("forced-alignment", Some(self.audio), self.control, |file| {
+                let mut form = reqwest::multipart::Form::new();
+                if let Some(file) = file {
+                    form = form.part("file", file);
+                }
+                form = form.text("text", text.clone());
+                if let Some(enabled) = enabled_spooled_file {
+                    form = form.text("enabled_spooled_file", enabled.to_string());
+                }
+                Ok(form)
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_stt_response() {
+        let json = r#"{
+            "characters": [{"text": "H", "start": 0.0, "end": 0.1}],
+            "words": [
+                {"text": "Hello", "start": 0.0, "end": 0.4, "loss": 0.1},
+                {"text": "world", "start": 0.6, "end": 1.0, "loss": 0.2}
+            ],
+            "loss": 0.15
+        }"#;
+        let alignment: ForcedAlignmentResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(alignment.characters.len(), 1);
+        assert_eq!(alignment.words[1].loss, Some(0.2));
+
+        let transcript = alignment.to_stt_response();
+        assert_eq!(transcript.text.as_deref(), Some("Hello world"));
+        let words = transcript.words.unwrap();
+        assert_eq!(words.len(), 3);
+        assert_eq!(words[1].type_field.as_deref(), Some("spacing"));
+        assert_eq!(words[1].start, Some(0.4));
+        assert_eq!(words[1].end, Some(0.6));
+    }
+
+    #[test]
+    fn test_validate() {
+        let client = ElevenLabsSTTClient::new("test-key".to_string());
+
+        let empty_text = client.forced_alignment(vec![1, 2, 3], " ");
+        assert!(empty_text.validate().is_err());
+
+        let empty_audio = client.forced_alignment(Vec::new(), "Hello");
+        assert!(empty_audio.validate().is_err());
+
+        assert!(client.forced_alignment(vec![1], "Hello").validate().is_ok());
+    }
+}
@@ -0,0 +1,595 @@
+//! Lightweight inspection of audio containers, reading headers only.
+
+use std::time::Duration;
+
+/// Sample rate of [`FileFormat::PcmS16le16`](crate::FileFormat::PcmS16le16) audio
+pub const PCM_SAMPLE_RATE: u32 = 16_000;
+
+/// Number of leading bytes needed to sniff a container
+pub(crate) const SNIFF_LEN: usize = 4096;
+
+/// Audio or video container recognized from its magic bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AudioFormat {
+    Wav,
+    Mp3,
+    Flac,
+    /// Ogg with Vorbis or an unrecognized codec
+    Ogg,
+    /// Ogg with Opus
+    Opus,
+    /// MP4 audio (`M4A ` brand)
+    M4a,
+    /// Any other ISO base media file: MP4, MOV, 3GP
+    Mp4,
+    WebM,
+    Matroska,
+    /// Raw AAC in ADTS frames
+    Aac,
+}
+
+impl AudioFormat {
+    /// Recognize the container from the first bytes of a file
+    pub fn sniff(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WAVE") {
+            return Some(AudioFormat::Wav);
+        }
+        if bytes.starts_with(b"fLaC") {
+            return Some(AudioFormat::Flac);
+        }
+        if bytes.starts_with(b"OggS") {
+            let segments = *bytes.get(26)? as usize;
+            let packet = bytes.get(27 + segments..).unwrap_or_default();
+            return Some(if packet.starts_with(b"OpusHead") {
+                AudioFormat::Opus
+            } else {
+                AudioFormat::Ogg
+            });
+        }
+        if bytes.get(4..8) == Some(b"ftyp") {
+            return Some(match bytes.get(8..12) {
+                Some(b"M4A ") | Some(b"M4B ") => AudioFormat::M4a,
+                _ => AudioFormat::Mp4,
+            });
+        }
+        if bytes.starts_with(b"\x1A\x45\xDF\xA3") {
+            // The EBML header names the document type near the start
+            let header = &bytes[..bytes.len().min(64)];
+            return Some(if header.windows(4).any(|w| w == b"webm") {
+                AudioFormat::WebM
+            } else {
+                AudioFormat::Matroska
+            });
+        }
+        if bytes.starts_with(b"ID3") || Mp3Frame::parse(bytes).is_some() {
+            return Some(AudioFormat::Mp3);
+        }
+        if bytes.len() >= 2 && bytes[0] == 0xFF && bytes[1] & 0xF6 == 0xF0 {
+            return Some(AudioFormat::Aac);
+        }
+
+        None
+    }
+
+    /// MIME type sent with the upload
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "audio/wav",
+            AudioFormat::Mp3 => "audio/mpeg",
+            AudioFormat::Flac => "audio/flac",
+            AudioFormat::Ogg => "audio/ogg",
+            AudioFormat::Opus => "audio/opus",
+            AudioFormat::M4a => "audio/mp4",
+            AudioFormat::Mp4 => "video/mp4",
+            AudioFormat::WebM => "video/webm",
+            AudioFormat::Matroska => "video/x-matroska",
+            AudioFormat::Aac => "audio/aac",
+        }
+    }
+
+    /// File name extension, without the dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "wav",
+            AudioFormat::Mp3 => "mp3",
+            AudioFormat::Flac => "flac",
+            AudioFormat::Ogg => "ogg",
+            AudioFormat::Opus => "opus",
+            AudioFormat::M4a => "m4a",
+            AudioFormat::Mp4 => "mp4",
+            AudioFormat::WebM => "webm",
+            AudioFormat::Matroska => "mkv",
+            AudioFormat::Aac => "aac",
+        }
+    }
+}
+
+/// Encode samples as 16-bit little-endian PCM, the layout of `FileFormat::PcmS16le16`
+pub fn pcm_s16le_from_i16(samples: &[i16]) -> Vec<u8> {
+    samples
+        .iter()
+        .flat_map(|sample| sample.to_le_bytes())
+        .collect()
+}
+
+/// Encode floating point samples in `[-1.0, 1.0]` as 16-bit little-endian PCM.
+/// Values outside the range are clipped, `NaN` becomes silence.
+pub fn pcm_s16le_from_f32(samples: &[f32]) -> Vec<u8> {
+    samples
+        .iter()
+        .flat_map(|sample| {
+            let sample = if sample.is_nan() { 0.0 } else { *sample };
+            ((sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16).to_le_bytes()
+        })
+        .collect()
+}
+
+/// Duration of raw mono 16kHz 16-bit PCM
+pub fn pcm_s16le_duration(bytes: &[u8]) -> Duration {
+    pcm_s16le_duration_of_len(bytes.len() as u64)
+}
+
+/// Duration of `len` bytes of raw mono 16kHz 16-bit PCM
+pub(crate) fn pcm_s16le_duration_of_len(len: u64) -> Duration {
+    Duration::from_secs_f64((len / 2) as f64 / PCM_SAMPLE_RATE as f64)
+}
+
+/// Describe payloads that are clearly not audio or video (documents, images, archives, text).
+/// Unrecognized binary data is given the benefit of the doubt and returns `None`.
+pub(crate) fn non_media_kind(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.is_empty() {
+        return Some("an empty file");
+    }
+    if AudioFormat::sniff(bytes).is_some() {
+        return None;
+    }
+
+    const SIGNATURES: [(&[u8], &str); 7] = [
+        (b"%PDF", "a PDF document"),
+        (b"PK\x03\x04", "a ZIP archive"),
+        (b"\x89PNG", "a PNG image"),
+        (b"\xFF\xD8\xFF", "a JPEG image"),
+        (b"GIF8", "a GIF image"),
+        (b"\x7FELF", "an executable"),
+        (b"\x1F\x8B", "a gzip archive"),
+    ];
+    if let Some((_, kind)) = SIGNATURES
+        .iter()
+        .find(|(signature, _)| bytes.starts_with(signature))
+    {
+        return Some(kind);
+    }
+
+    // A truncated multi-byte character at the end of the sample is still text
+    let sample = &bytes[..bytes.len().min(512)];
+    let text = match std::str::from_utf8(sample) {
+        Ok(text) => text,
+        Err(e) if e.error_len().is_none() => {
+            std::str::from_utf8(&sample[..e.valid_up_to()]).ok()?
+        }
+        Err(_) => return None,
+    };
+    text.chars()
+        .all(|c| !c.is_control() || c.is_whitespace())
+        .then_some("text")
+}
+
+/// Duration of WAV, MP3, FLAC or Ogg (Vorbis/Opus) audio, read from its headers.
+/// Returns `None` for other formats or when the headers are incomplete.
+pub fn duration(bytes: &[u8]) -> Option<Duration> {
+    let seconds = match AudioFormat::sniff(bytes)? {
+        AudioFormat::Wav => wav_duration(bytes, bytes.len() as u64)?,
+        AudioFormat::Flac => flac_duration(bytes)?,
+        AudioFormat::Ogg | AudioFormat::Opus => ogg_duration(bytes)?,
+        AudioFormat::Mp3 => mp3_duration(bytes)?,
+        _ => return None,
+    };
+
+    seconds_to_duration(seconds)
+}
+
+/// Duration of WAV or FLAC audio from the start of a file and its total size in bytes,
+/// for files streamed from disk. MP3 and Ogg need the whole file and return `None`.
+pub(crate) fn header_duration(header: &[u8], file_len: u64) -> Option<Duration> {
+    let seconds = match AudioFormat::sniff(header)? {
+        AudioFormat::Wav => wav_duration(header, file_len)?,
+        AudioFormat::Flac => flac_duration(header)?,
+        _ => return None,
+    };
+
+    seconds_to_duration(seconds)
+}
+
+fn seconds_to_duration(seconds: f64) -> Option<Duration> {
+    (seconds.is_finite() && seconds >= 0.0).then(|| Duration::from_secs_f64(seconds))
+}
+
+fn u16_le(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn u32_le(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn u32_be(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn i64_le(bytes: &[u8], at: usize) -> Option<i64> {
+    Some(i64::from_le_bytes(bytes.get(at..at + 8)?.try_into().ok()?))
+}
+
+/// `data` chunk size divided by the byte rate from the `fmt ` chunk.
+/// `bytes` may be only the start of a file of `file_len` bytes.
+fn wav_duration(bytes: &[u8], file_len: u64) -> Option<f64> {
+    let mut offset = 12;
+    let mut byte_rate = None;
+
+    while offset + 8 <= bytes.len() {
+        let id = &bytes[offset..offset + 4];
+        let size = u32_le(bytes, offset + 4)? as usize;
+        let body = offset + 8;
+
+        match id {
+            b"fmt " => byte_rate = Some(u32_le(bytes, body + 8)?),
+            b"data" => {
+                let byte_rate = byte_rate.filter(|rate| *rate > 0)?;
+                // Streamed WAVs may carry a placeholder size, fall back to the file size
+                let available = file_len.saturating_sub(body as u64);
+                let size = size as u64;
+                let size = if size == u32::MAX as u64 || size > available {
+                    available
+                } else {
+                    size
+                };
+                return Some(size as f64 / byte_rate as f64);
+            }
+            _ => {}
+        }
+
+        // Chunks are padded to an even size
+        offset = body.checked_add(size)?.checked_add(size % 2)?;
+    }
+
+    None
+}
+
+/// Total samples over sample rate from the STREAMINFO block
+fn flac_duration(bytes: &[u8]) -> Option<f64> {
+    // STREAMINFO is always the first metadata block
+    if bytes.get(4)? & 0x7F != 0 {
+        return None;
+    }
+    let info = bytes.get(8..8 + 34)?;
+
+    let sample_rate =
+        ((info[10] as u32) << 12) | ((info[11] as u32) << 4) | ((info[12] as u32) >> 4);
+    let total_samples = (((info[13] & 0x0F) as u64) << 32) | u32_be(info, 14)? as u64;
+
+    if sample_rate == 0 || total_samples == 0 {
+        return None;
+    }
+    Some(total_samples as f64 / sample_rate as f64)
+}
+
+/// Granule position of the last page over the sample rate of the first packet
+fn ogg_duration(bytes: &[u8]) -> Option<f64> {
+    let segments = *bytes.get(26)? as usize;
+    let packet = bytes.get(27 + segments..)?;
+
+    let (sample_rate, pre_skip) = if packet.starts_with(b"\x01vorbis") {
+        (u32_le(packet, 12)?, 0)
+    } else if packet.starts_with(b"OpusHead") {
+        // Opus granule positions always count 48kHz samples
+        (48_000, u16_le(packet, 10)? as i64)
+    } else {
+        return None;
+    };
+
+    let last_page = (0..=bytes.len().saturating_sub(27))
+        .rev()
+        .find(|&at| bytes[at..].starts_with(b"OggS"))?;
+    let granule = i64_le(bytes, last_page + 6)?;
+
+    if sample_rate == 0 || granule <= pre_skip {
+        return None;
+    }
+    Some((granule - pre_skip) as f64 / sample_rate as f64)
+}
+
+/// MPEG audio frame header fields needed for timing
+struct Mp3Frame {
+    mpeg1: bool,
+    mono: bool,
+    bitrate: u32,
+    sample_rate: u32,
+    samples_per_frame: u32,
+    padding: bool,
+}
+
+impl Mp3Frame {
+    /// Parse a Layer III frame header
+    fn parse(header: &[u8]) -> Option<Self> {
+        const BITRATES_V1: [u32; 15] = [
+            0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+        ];
+        const BITRATES_V2: [u32; 15] =
+            [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+
+        let header = header.get(..4)?;
+        if header[0] != 0xFF || header[1] & 0xE0 != 0xE0 {
+            return None;
+        }
+
+        let version = (header[1] >> 3) & 0x03;
+        let layer = (header[1] >> 1) & 0x03;
+        let bitrate_index = (header[2] >> 4) as usize;
+        let sample_rate_index = ((header[2] >> 2) & 0x03) as usize;
+        if version == 1 || layer != 1 || bitrate_index == 0 || bitrate_index == 15 {
+            return None;
+        }
+
+        let mpeg1 = version == 3;
+        let base_rate = *[44_100, 48_000, 32_000].get(sample_rate_index)?;
+        let sample_rate = match version {
+            3 => base_rate,
+            2 => base_rate / 2,
+            _ => base_rate / 4,
+        };
+        let bitrates = if mpeg1 { &BITRATES_V1 } else { &BITRATES_V2 };
+
+        Some(Self {
+            mpeg1,
+            mono: header[3] >> 6 == 3,
+            bitrate: bitrates[bitrate_index] * 1000,
+            sample_rate,
+            samples_per_frame: if mpeg1 { 1152 } else { 576 },
+            padding: header[2] & 0x02 != 0,
+        })
+    }
+
+    /// Frame size in bytes, including the header
+    fn length(&self) -> usize {
+        (self.samples_per_frame / 8 * self.bitrate / self.sample_rate) as usize
+            + self.padding as usize
+    }
+
+    /// Offset of the Xing/Info tag, right after the side information
+    fn xing_offset(&self) -> usize {
+        4 + match (self.mpeg1, self.mono) {
+            (true, false) => 32,
+            (true, true) | (false, false) => 17,
+            (false, true) => 9,
+        }
+    }
+}
+
+/// Frame count from a Xing/Info or VBRI header, or a constant bitrate estimate
+fn mp3_duration(bytes: &[u8]) -> Option<f64> {
+    let mut start = 0;
+    if bytes.starts_with(b"ID3") {
+        let header = bytes.get(..10)?;
+        let size = header[6..10]
+            .iter()
+            .fold(0usize, |size, byte| (size << 7) | (byte & 0x7F) as usize);
+        let footer = if header[5] & 0x10 != 0 { 10 } else { 0 };
+        start = 10 + size + footer;
+    }
+
+    // A frame sync is only trusted when the next frame follows it (or the data ends)
+    let offset = (start..bytes.len().saturating_sub(4)).find(|&at| {
+        Mp3Frame::parse(&bytes[at..]).is_some_and(|frame| {
+            let next = at + frame.length();
+            next + 4 > bytes.len() || Mp3Frame::parse(&bytes[next..]).is_some()
+        })
+    })?;
+    let frame = Mp3Frame::parse(&bytes[offset..])?;
+    let seconds_per_frame = frame.samples_per_frame as f64 / frame.sample_rate as f64;
+
+    let xing = offset + frame.xing_offset();
+    if let Some(tag) = bytes.get(xing..xing + 4)
+        && (tag == b"Xing" || tag == b"Info")
+        && u32_be(bytes, xing + 4)? & 0x01 != 0
+    {
+        return Some(u32_be(bytes, xing + 8)? as f64 * seconds_per_frame);
+    }
+
+    let vbri = offset + 4 + 32;
+    if bytes.get(vbri..vbri + 4) == Some(b"VBRI") {
+        return Some(u32_be(bytes, vbri + 14)? as f64 * seconds_per_frame);
+    }
+
+    let mut end = bytes.len();
+    if end >= offset + 128 && bytes[end - 128..].starts_with(b"TAG") {
+        end -= 128;
+    }
+    Some((end - offset) as f64 * 8.0 / frame.bitrate as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 16-bit mono PCM WAV with `samples` silent samples
+    fn wav(sample_rate: u32, samples: u32) -> Vec<u8> {
+        let data_size = samples * 2;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_size).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&sample_rate.to_le_bytes());
+        bytes.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+        bytes.extend_from_slice(&2u16.to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_size.to_le_bytes());
+        bytes.resize(bytes.len() + data_size as usize, 0);
+        bytes
+    }
+
+    fn ogg_page(granule: i64, packet: &[u8]) -> Vec<u8> {
+        let mut page = b"OggS\x00\x02".to_vec();
+        page.extend_from_slice(&granule.to_le_bytes());
+        page.extend_from_slice(&[0; 12]);
+        page.push(1);
+        page.push(packet.len() as u8);
+        page.extend_from_slice(packet);
+        page
+    }
+
+    #[test]
+    fn test_wav_duration() {
+        assert_eq!(
+            duration(&wav(16_000, 24_000)),
+            Some(Duration::from_millis(1500))
+        );
+    }
+
+    #[test]
+    fn test_other_containers_have_no_duration() {
+        // Random payload full of MP3-looking frame syncs behind an M4A header
+        let mut m4a = b"\0\0\0\x20ftypM4A \0\0\0\0".to_vec();
+        let frame = [0xFF, 0xFB, 0x90, 0x00];
+        while m4a.len() < 32_000 {
+            m4a.extend_from_slice(&frame);
+            m4a.resize(m4a.len() + 413, 0);
+        }
+        assert_eq!(AudioFormat::sniff(&m4a), Some(AudioFormat::M4a));
+        assert_eq!(duration(&m4a), None);
+
+        let mut webm = b"\x1A\x45\xDF\xA3webm".to_vec();
+        webm.extend_from_slice(&m4a[16..]);
+        assert_eq!(duration(&webm), None);
+    }
+
+    #[test]
+    fn test_header_duration() {
+        let wav = wav(16_000, 24_000);
+        assert_eq!(
+            header_duration(&wav[..SNIFF_LEN], wav.len() as u64),
+            Some(Duration::from_millis(1500))
+        );
+        // The data chunk size is trusted over the file size
+        assert_eq!(
+            header_duration(&wav[..SNIFF_LEN], wav.len() as u64 * 2),
+            Some(Duration::from_millis(1500))
+        );
+
+        let mp3 = std::fs::read("inputs/speech.mp3").unwrap();
+        assert_eq!(header_duration(&mp3[..SNIFF_LEN], mp3.len() as u64), None);
+    }
+
+    #[test]
+    fn test_flac_duration() {
+        let mut bytes = b"fLaC\x80\x00\x00\x22".to_vec();
+        let mut info = [0u8; 34];
+        // 44.1kHz, 88200 samples
+        info[10] = (44_100u32 >> 12) as u8;
+        info[11] = (44_100u32 >> 4) as u8;
+        info[12] = ((44_100u32 & 0x0F) << 4) as u8;
+        info[14..18].copy_from_slice(&88_200u32.to_be_bytes());
+        bytes.extend_from_slice(&info);
+
+        assert_eq!(duration(&bytes), Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_ogg_duration() {
+        let mut head = b"OpusHead\x01\x01".to_vec();
+        head.extend_from_slice(&312u16.to_le_bytes());
+        head.extend_from_slice(&48_000u32.to_le_bytes());
+        let mut bytes = ogg_page(0, &head);
+        bytes.extend(ogg_page(96_312, b"audio"));
+
+        assert_eq!(duration(&bytes), Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_mp3_duration() {
+        // MPEG1 Layer III, 128kbps, 44.1kHz, stereo, no Xing header
+        let mut bytes = b"ID3\x04\x00\x00\x00\x00\x00\x02xx".to_vec();
+        let frame = [0xFF, 0xFB, 0x90, 0x00];
+        let audio_start = bytes.len();
+        while bytes.len() - audio_start < 32_000 {
+            bytes.extend_from_slice(&frame);
+            bytes.resize(bytes.len() + 413, 0);
+        }
+        bytes.truncate(audio_start + 32_000);
+        assert_eq!(duration(&bytes), Some(Duration::from_secs(2)));
+
+        // Same stream with an Xing header announcing 100 frames
+        let mut vbr = frame.to_vec();
+        vbr.resize(36, 0);
+        vbr.extend_from_slice(b"Xing");
+        vbr.extend_from_slice(&1u32.to_be_bytes());
+        vbr.extend_from_slice(&100u32.to_be_bytes());
+        let seconds = duration(&vbr).unwrap().as_secs_f64();
+        assert!((seconds - 100.0 * 1152.0 / 44_100.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sniff() {
+        let speech = std::fs::read("inputs/speech.mp3").unwrap();
+        assert_eq!(AudioFormat::sniff(&speech), Some(AudioFormat::Mp3));
+        assert_eq!(AudioFormat::sniff(&wav(8000, 1)), Some(AudioFormat::Wav));
+        assert_eq!(
+            AudioFormat::sniff(b"\0\0\0\x20ftypM4A \0\0\0\0"),
+            Some(AudioFormat::M4a)
+        );
+        assert_eq!(
+            AudioFormat::sniff(b"\0\0\0\x18ftypisom"),
+            Some(AudioFormat::Mp4)
+        );
+        assert_eq!(
+            AudioFormat::sniff(b"\x1A\x45\xDF\xA3\x9F\x42\x82\x84webm"),
+            Some(AudioFormat::WebM)
+        );
+        assert_eq!(
+            AudioFormat::sniff(&ogg_page(0, b"OpusHead\x01\x01")),
+            Some(AudioFormat::Opus)
+        );
+        assert_eq!(
+            AudioFormat::sniff(b"\xFF\xF1\x50\x80"),
+            Some(AudioFormat::Aac)
+        );
+        assert_eq!(AudioFormat::Opus.mime_type(), "audio/opus");
+        assert_eq!(AudioFormat::Matroska.extension(), "mkv");
+    }
+
+    #[test]
+    fn test_non_media_kind() {
+        assert_eq!(non_media_kind(b""), Some("an empty file"));
+        assert_eq!(non_media_kind(b"%PDF-1.7"), Some("a PDF document"));
+        assert_eq!(
+            non_media_kind(b"{\"error\": \"not found\"}\n"),
+            Some("text")
+        );
+        assert_eq!(non_media_kind("caf\u{e9}".as_bytes()), Some("text"));
+        assert_eq!(non_media_kind(&[0u8; 16]), None);
+        assert_eq!(non_media_kind(&wav(8000, 1)), None);
+    }
+
+    #[test]
+    fn test_pcm_conversion() {
+        assert_eq!(pcm_s16le_from_i16(&[1, -2]), vec![0x01, 0x00, 0xFE, 0xFF]);
+        assert_eq!(
+            pcm_s16le_from_f32(&[0.0, 1.0, -1.0, 2.0, f32::NAN]),
+            pcm_s16le_from_i16(&[0, 32767, -32767, 32767, 0])
+        );
+        assert_eq!(
+            pcm_s16le_duration(&vec![0; 48_000]),
+            Duration::from_millis(1500)
+        );
+    }
+
+    #[test]
+    fn test_unknown_format() {
+        assert_eq!(duration(b"not audio"), None);
+        assert_eq!(duration(b""), None);
+    }
+}
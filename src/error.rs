@@ -1,4 +1,6 @@
+use serde::Deserialize;
 use std::fmt;
+use std::time::Duration;
 
 /// All possible errors that can occur when using the ElevenLabs API
 #[derive(Debug)]
@@ -7,7 +9,11 @@ pub enum ElevenLabsSTTError {
     RequestError(reqwest::Error),
 
     /// API returned an error status code
-    ApiError { status: u16, message: String },
+    ApiError {
+        status: u16,
+        message: String,
+        retry_after: Option<u64>, // seconds, as sent with 503 and other 5xx responses
+    },
 
     /// Failed to parse JSON response
     ParseError(reqwest::Error),
@@ -26,13 +32,198 @@ pub enum ElevenLabsSTTError {
 
     /// Invalid input parameters
     ValidationError(String),
+
+    /// Failed to read the file to upload
+    IoError(std::io::Error),
+
+    /// The request was cancelled through its cancellation token
+    Cancelled,
+
+    /// Gave up waiting for a result after the given duration
+    Timeout(Duration),
+
+    /// The estimated cost of the request is above the configured budget
+    BudgetExceeded {
+        estimated_credits: f64,
+        max_credits: f64,
+    },
+
+    /// A webhook delivery failed signature verification or could not be decoded
+    WebhookError(String),
+}
+
+/// Error body returned by the ElevenLabs API: `{"detail": {"status": "...", "message": "..."}}`.
+/// Validation failures return a list of `{"loc": [...], "msg": "..."}` entries instead.
+#[derive(Debug, Deserialize)]
+struct ApiErrorBody {
+    detail: ApiErrorDetail,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ApiErrorDetail {
+    Object {
+        status: Option<String>,
+        message: Option<String>,
+    },
+    List(Vec<ApiValidationDetail>),
+    Text(String),
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiValidationDetail {
+    #[serde(default)]
+    loc: Vec<serde_json::Value>,
+    msg: Option<String>,
+}
+
+impl ElevenLabsSTTError {
+    /// Build a typed error from a non-2xx HTTP response
+    pub(crate) async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status().as_u16();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_retry_after);
+        let body = response.text().await.unwrap_or_default();
+
+        Self::from_status(status, retry_after, &body)
+    }
+
+    /// Classify an error status and body into the matching variant
+    pub(crate) fn from_status(status: u16, retry_after: Option<u64>, body: &str) -> Self {
+        let (detail_status, message) = parse_error_body(body);
+        let message = message.unwrap_or_else(|| body.to_string());
+
+        if detail_status.as_deref() == Some("quota_exceeded") {
+            return ElevenLabsSTTError::QuotaExceededError(message);
+        }
+
+        match status {
+            401 => ElevenLabsSTTError::AuthenticationError(message),
+            402 => ElevenLabsSTTError::QuotaExceededError(message),
+            422 => ElevenLabsSTTError::ValidationError(message),
+            429 => ElevenLabsSTTError::RateLimitError {
+                retry_after,
+                message,
+            },
+            _ => ElevenLabsSTTError::ApiError {
+                status,
+                message,
+                retry_after,
+            },
+        }
+    }
+
+    /// Whether the error is transient and the request may succeed if retried
+    /// (network failures, rate limits and 5xx responses)
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ElevenLabsSTTError::RequestError(e) => !e.is_builder() && !e.is_decode(),
+            ElevenLabsSTTError::ApiError { status, .. } => *status == 429 || *status >= 500,
+            ElevenLabsSTTError::RateLimitError { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the request may have reached the API before failing: a timeout, or a body
+    /// error once the upload had started. Re-sending a transcription after such an error
+    /// can get it processed and billed twice.
+    pub fn may_have_been_processed(&self) -> bool {
+        match self {
+            ElevenLabsSTTError::RequestError(e) => e.is_timeout() || e.is_body(),
+            _ => false,
+        }
+    }
+
+    /// How long the API asked us to wait before retrying, if known
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ElevenLabsSTTError::RateLimitError {
+                retry_after: Some(seconds),
+                ..
+            }
+            | ElevenLabsSTTError::ApiError {
+                retry_after: Some(seconds),
+                ..
+            } => Some(Duration::from_secs(*seconds)),
+            _ => None,
+        }
+    }
+}
+
+/// Whether `body` is a `{"detail": {"status": ...}}` error produced by the ElevenLabs API
+/// itself, as opposed to a proxy, or the framework's `{"detail": "Not Found"}` for an unknown
+/// route (e.g. a base URL missing `/v1`)
+pub(crate) fn is_api_error_body(body: &str) -> bool {
+    matches!(
+        serde_json::from_str::<ApiErrorBody>(body),
+        Ok(ApiErrorBody {
+            detail: ApiErrorDetail::Object {
+                status: Some(_),
+                ..
+            }
+        })
+    )
+}
+
+/// Extract `detail.status` and a human readable message from an API error body
+fn parse_error_body(body: &str) -> (Option<String>, Option<String>) {
+    let Ok(parsed) = serde_json::from_str::<ApiErrorBody>(body) else {
+        return (None, None);
+    };
+
+    match parsed.detail {
+        ApiErrorDetail::Object { status, message } => (status, message),
+        ApiErrorDetail::Text(message) => (None, Some(message)),
+        ApiErrorDetail::List(details) => {
+            let message = details
+                .iter()
+                .map(|detail| {
+                    let loc = detail
+                        .loc
+                        .iter()
+                        .map(|part| match part {
+                            serde_json::Value::String(s) => s.clone(),
+                            other => other.to_string(),
+                        })
+                        .collect::<Vec<_>>()
+                        .join(".");
+                    let msg = detail.msg.as_deref().unwrap_or("invalid value");
+                    if loc.is_empty() {
+                        msg.to_string()
+                    } else {
+                        format!("{}: {}", loc, msg)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("; ");
+            (None, Some(message))
+        }
+    }
+}
+
+/// Parse a `Retry-After` header given either as delay-seconds or as an HTTP date
+fn parse_retry_after(value: &str) -> Option<u64> {
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(seconds);
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let seconds = (date.with_timezone(&chrono::Utc) - chrono::Utc::now()).num_seconds();
+    Some(seconds.max(0) as u64)
 }
 
 impl fmt::Display for ElevenLabsSTTError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ElevenLabsSTTError::RequestError(e) => write!(f, "Request failed: {}", e),
-            ElevenLabsSTTError::ApiError { status, message } => {
+            ElevenLabsSTTError::ApiError {
+                status, message, ..
+            } => {
                 write!(f, "API error ({}): {}", status, message)
             }
             ElevenLabsSTTError::ParseError(e) => write!(f, "Failed to parse response: {}", e),
@@ -52,6 +243,18 @@ impl fmt::Display for ElevenLabsSTTError {
             },
             ElevenLabsSTTError::QuotaExceededError(msg) => write!(f, "Quota exceeded: {}", msg),
             ElevenLabsSTTError::ValidationError(msg) => write!(f, "Validation error: {}", msg),
+            ElevenLabsSTTError::IoError(e) => write!(f, "I/O error: {}", e),
+            ElevenLabsSTTError::Cancelled => write!(f, "Request was cancelled"),
+            ElevenLabsSTTError::Timeout(after) => write!(f, "Timed out after {:?}", after),
+            ElevenLabsSTTError::BudgetExceeded {
+                estimated_credits,
+                max_credits,
+            } => write!(
+                f,
+                "Estimated cost of {:.1} credits exceeds the budget of {:.1} credits",
+                estimated_credits, max_credits
+            ),
+            ElevenLabsSTTError::WebhookError(msg) => write!(f, "Webhook error: {}", msg),
         }
     }
 }
@@ -61,6 +264,7 @@ impl std::error::Error for ElevenLabsSTTError {
         match self {
             ElevenLabsSTTError::RequestError(e) => Some(e),
             ElevenLabsSTTError::ParseError(e) => Some(e),
+            ElevenLabsSTTError::IoError(e) => Some(e),
             _ => None,
         }
     }
@@ -84,6 +288,7 @@ impl From<reqwest::Error> for ElevenLabsSTTError {
                 _ => ElevenLabsSTTError::ApiError {
                     status: status_code,
                     message: error.to_string(),
+                    retry_after: None,
                 },
             }
         } else {
@@ -91,3 +296,57 @@ impl From<reqwest::Error> for ElevenLabsSTTError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_status_classification() {
+        let body = r#"{"detail":{"status":"invalid_api_key","message":"Invalid API key"}}"#;
+        match ElevenLabsSTTError::from_status(401, None, body) {
+            ElevenLabsSTTError::AuthenticationError(msg) => assert_eq!(msg, "Invalid API key"),
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        let body = r#"{"detail":{"status":"quota_exceeded","message":"Not enough credits"}}"#;
+        assert!(matches!(
+            ElevenLabsSTTError::from_status(401, None, body),
+            ElevenLabsSTTError::QuotaExceededError(_)
+        ));
+
+        match ElevenLabsSTTError::from_status(429, Some(12), "slow down") {
+            ElevenLabsSTTError::RateLimitError {
+                retry_after,
+                message,
+            } => {
+                assert_eq!(retry_after, Some(12));
+                assert_eq!(message, "slow down");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        let body = r#"{"detail":[{"loc":["body","temperature"],"msg":"must be <= 2"}]}"#;
+        match ElevenLabsSTTError::from_status(422, None, body) {
+            ElevenLabsSTTError::ValidationError(msg) => {
+                assert_eq!(msg, "body.temperature: must be <= 2")
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        assert!(matches!(
+            ElevenLabsSTTError::from_status(500, None, "boom"),
+            ElevenLabsSTTError::ApiError { status: 500, .. }
+        ));
+
+        let unavailable = ElevenLabsSTTError::from_status(503, Some(4), "maintenance");
+        assert_eq!(unavailable.retry_after(), Some(Duration::from_secs(4)));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("30"), Some(30));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), Some(0));
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+}
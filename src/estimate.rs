@@ -0,0 +1,71 @@
+//! Pre-flight cost estimation from the audio duration and configured pricing.
+
+use crate::models::Model;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Credits charged per hour of audio, with optional per-model overrides.
+///
+/// Prices depend on the plan, so they are configured by the caller
+/// rather than built into the crate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pricing {
+    credits_per_hour: f64,
+    models: HashMap<String, f64>,
+}
+
+impl Pricing {
+    /// Charge `credits_per_hour` for every model without an override
+    pub fn per_hour(credits_per_hour: f64) -> Self {
+        Self {
+            credits_per_hour,
+            models: HashMap::new(),
+        }
+    }
+
+    /// Override the hourly price of a single model
+    pub fn model<M: Into<Model>>(mut self, model: M, credits_per_hour: f64) -> Self {
+        self.models
+            .insert(model.into().as_str().to_string(), credits_per_hour);
+        self
+    }
+
+    /// Hourly price of `model`
+    pub fn credits_per_hour(&self, model: &Model) -> f64 {
+        self.models
+            .get(model.as_str())
+            .copied()
+            .unwrap_or(self.credits_per_hour)
+    }
+
+    /// Credits charged for transcribing `duration` of audio with `model`
+    pub fn cost(&self, model: &Model, duration: Duration) -> f64 {
+        self.credits_per_hour(model) * duration.as_secs_f64() / 3600.0
+    }
+}
+
+/// Estimated duration and cost of a request
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostEstimate {
+    pub model: Model,
+    pub duration: Duration,
+    pub credits: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pricing() {
+        let pricing = Pricing::per_hour(1000.0).model(Model::ScribeV1Experimental, 2000.0);
+        let half_hour = Duration::from_secs(30 * 60);
+
+        assert_eq!(pricing.cost(&Model::ScribeV1, half_hour), 500.0);
+        assert_eq!(
+            pricing.cost(&Model::ScribeV1Experimental, half_hour),
+            1000.0
+        );
+        assert_eq!(pricing.credits_per_hour(&Model::from("custom")), 1000.0);
+    }
+}
@@ -0,0 +1,263 @@
+//! Additional export formats rendered by the API alongside the transcript.
+
+use base64::Engine;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Segmentation options shared by every export format
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExportOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_speakers: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_timestamps: Option<bool>,
+    /// Start a new segment after a silence longer than this many seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub segment_on_silence_longer_than_s: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_segment_duration_s: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_segment_chars: Option<u32>,
+}
+
+impl ExportOptions {
+    /// Create empty options, leaving every setting to the API default
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Label segments with their speaker
+    pub fn include_speakers(mut self, include_speakers: bool) -> Self {
+        self.include_speakers = Some(include_speakers);
+        self
+    }
+
+    /// Prefix segments with their timestamps
+    pub fn include_timestamps(mut self, include_timestamps: bool) -> Self {
+        self.include_timestamps = Some(include_timestamps);
+        self
+    }
+
+    /// Start a new segment after a silence longer than `seconds`
+    pub fn segment_on_silence_longer_than(mut self, seconds: f64) -> Self {
+        self.segment_on_silence_longer_than_s = Some(seconds);
+        self
+    }
+
+    /// Set the longest duration of a segment in seconds
+    pub fn max_segment_duration(mut self, seconds: f64) -> Self {
+        self.max_segment_duration_s = Some(seconds);
+        self
+    }
+
+    /// Set the most characters in a segment
+    pub fn max_segment_chars(mut self, max_segment_chars: u32) -> Self {
+        self.max_segment_chars = Some(max_segment_chars);
+        self
+    }
+}
+
+/// Options of line based formats (SRT, plain text), which can also wrap lines
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TextExportOptions {
+    #[serde(flatten)]
+    pub segments: ExportOptions,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_characters_per_line: Option<u32>,
+}
+
+impl TextExportOptions {
+    /// Create empty options, leaving every setting to the API default
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the segmentation options
+    pub fn segments(mut self, segments: ExportOptions) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Wrap lines longer than `max_characters_per_line`
+    pub fn max_characters_per_line(mut self, max_characters_per_line: u32) -> Self {
+        self.max_characters_per_line = Some(max_characters_per_line);
+        self
+    }
+}
+
+/// Extra format to render server-side, sent as `additional_formats`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "format", rename_all = "snake_case")]
+pub enum AdditionalFormat {
+    Srt(TextExportOptions),
+    Txt(TextExportOptions),
+    SegmentedJson(ExportOptions),
+    Docx(ExportOptions),
+    Pdf(ExportOptions),
+    Html(ExportOptions),
+}
+
+impl AdditionalFormat {
+    /// Format name sent to the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdditionalFormat::Srt(_) => "srt",
+            AdditionalFormat::Txt(_) => "txt",
+            AdditionalFormat::SegmentedJson(_) => "segmented_json",
+            AdditionalFormat::Docx(_) => "docx",
+            AdditionalFormat::Pdf(_) => "pdf",
+            AdditionalFormat::Html(_) => "html",
+        }
+    }
+}
+
+/// Document rendered by the API, with base64 content already decoded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "RawExportedFormat", into = "RawExportedFormat")]
+pub struct ExportedFormat {
+    pub requested_format: String,
+    pub file_extension: String,
+    pub content_type: String,
+    /// Decoded content, or the content as received if it could not be decoded
+    pub content: Vec<u8>,
+    /// Whether the API sent the content base64 encoded (binary formats like docx and pdf)
+    pub is_base64_encoded: bool,
+    /// Why base64 content could not be decoded, in which case `content` is left as received
+    pub decode_error: Option<String>,
+}
+
+impl ExportedFormat {
+    /// Content as text, for text formats like srt, txt, html and segmented_json.
+    /// Returns `None` when the content is not UTF-8, e.g. for docx and pdf.
+    pub fn text(&self) -> Option<&str> {
+        std::str::from_utf8(&self.content).ok()
+    }
+}
+
+/// `additional_formats` entry as sent over the wire
+#[derive(Serialize, Deserialize)]
+struct RawExportedFormat {
+    requested_format: String,
+    file_extension: String,
+    content_type: String,
+    #[serde(default)]
+    is_base64_encoded: bool,
+    content: String,
+}
+
+impl From<RawExportedFormat> for ExportedFormat {
+    fn from(raw: RawExportedFormat) -> Self {
+        let decoded = if raw.is_base64_encoded {
+            base64::engine::general_purpose::STANDARD
+                .decode(raw.content.trim())
+                .map_err(|e| format!("invalid base64 {} content: {}", raw.requested_format, e))
+        } else {
+            Ok(raw.content.clone().into_bytes())
+        };
+        let (content, decode_error) = match decoded {
+            Ok(content) => (content, None),
+            Err(error) => (raw.content.into_bytes(), Some(error)),
+        };
+
+        ExportedFormat {
+            requested_format: raw.requested_format,
+            file_extension: raw.file_extension,
+            content_type: raw.content_type,
+            content,
+            is_base64_encoded: raw.is_base64_encoded,
+            decode_error,
+        }
+    }
+}
+
+impl From<ExportedFormat> for RawExportedFormat {
+    fn from(format: ExportedFormat) -> Self {
+        let content = if format.is_base64_encoded && format.decode_error.is_none() {
+            base64::engine::general_purpose::STANDARD.encode(&format.content)
+        } else {
+            String::from_utf8_lossy(&format.content).into_owned()
+        };
+
+        RawExportedFormat {
+            requested_format: format.requested_format,
+            file_extension: format.file_extension,
+            content_type: format.content_type,
+            is_base64_encoded: format.is_base64_encoded,
+            content,
+        }
+    }
+}
+
+/// Decode `additional_formats` entry by entry, skipping `null` and malformed entries,
+/// so a bad document never makes the whole transcript undecodable
+pub(crate) fn deserialize_exported_formats<'de, D>(
+    deserializer: D,
+) -> Result<Option<Vec<ExportedFormat>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let entries = Option::<Vec<serde_json::Value>>::deserialize(deserializer)?;
+    Ok(entries.map(|entries| {
+        entries
+            .into_iter()
+            .filter_map(|entry| serde_json::from_value(entry).ok())
+            .collect()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_additional_formats() {
+        let formats = vec![
+            AdditionalFormat::Srt(
+                TextExportOptions::new()
+                    .max_characters_per_line(42)
+                    .segments(ExportOptions::new().include_speakers(true)),
+            ),
+            AdditionalFormat::Pdf(ExportOptions::new()),
+        ];
+
+        assert_eq!(
+            serde_json::to_string(&formats).unwrap(),
+            r#"[{"format":"srt","include_speakers":true,"max_characters_per_line":42},{"format":"pdf"}]"#
+        );
+        assert_eq!(formats[1].as_str(), "pdf");
+    }
+
+    #[test]
+    fn test_decode_exported_formats() {
+        let json = r#"[
+            {"requested_format": "txt", "file_extension": "txt", "content_type": "text/plain",
+             "is_base64_encoded": false, "content": "Hello world"},
+            {"requested_format": "pdf", "file_extension": "pdf", "content_type": "application/pdf",
+             "is_base64_encoded": true, "content": "JVBERi0xLjc="}
+        ]"#;
+        let formats: Vec<ExportedFormat> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(formats[0].text().unwrap(), "Hello world");
+        assert_eq!(formats[1].content, b"%PDF-1.7");
+
+        let binary = ExportedFormat {
+            content: vec![0xFF, 0xFE],
+            ..formats[1].clone()
+        };
+        assert_eq!(binary.text(), None);
+
+        let round_trip: Vec<ExportedFormat> =
+            serde_json::from_str(&serde_json::to_string(&formats).unwrap()).unwrap();
+        assert_eq!(round_trip, formats);
+
+        let invalid = r#"{"requested_format": "pdf", "file_extension": "pdf",
+            "content_type": "application/pdf", "is_base64_encoded": true, "content": "%%%"}"#;
+        let invalid: ExportedFormat = serde_json::from_str(invalid).unwrap();
+        assert_eq!(invalid.content, b"%%%");
+        assert!(invalid.decode_error.is_some());
+        assert!(
+            serde_json::to_string(&invalid)
+                .unwrap()
+                .contains(r#""content":"%%%""#)
+        );
+    }
+}
@@ -0,0 +1,436 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// A known language: ISO 639-1 code (if any), ISO 639-3 code and English name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Language {
+    pub iso639_1: Option<&'static str>,
+    pub iso639_3: &'static str,
+    pub english_name: &'static str,
+}
+
+const fn lang(
+    iso639_1: Option<&'static str>,
+    iso639_3: &'static str,
+    english_name: &'static str,
+) -> Language {
+    Language {
+        iso639_1,
+        iso639_3,
+        english_name,
+    }
+}
+
+/// Every ISO 639-1 language, plus the ISO 639-3-only languages supported by Scribe
+pub const LANGUAGES: &[Language] = &[
+    lang(Some("aa"), "aar", "Afar"),
+    lang(Some("ab"), "abk", "Abkhazian"),
+    lang(Some("ae"), "ave", "Avestan"),
+    lang(Some("af"), "afr", "Afrikaans"),
+    lang(Some("ak"), "aka", "Akan"),
+    lang(Some("am"), "amh", "Amharic"),
+    lang(Some("an"), "arg", "Aragonese"),
+    lang(Some("ar"), "ara", "Arabic"),
+    lang(Some("as"), "asm", "Assamese"),
+    lang(None, "ast", "Asturian"),
+    lang(Some("av"), "ava", "Avaric"),
+    lang(Some("ay"), "aym", "Aymara"),
+    lang(Some("az"), "aze", "Azerbaijani"),
+    lang(Some("ba"), "bak", "Bashkir"),
+    lang(Some("be"), "bel", "Belarusian"),
+    lang(Some("bg"), "bul", "Bulgarian"),
+    lang(Some("bi"), "bis", "Bislama"),
+    lang(Some("bm"), "bam", "Bambara"),
+    lang(Some("bn"), "ben", "Bengali"),
+    lang(Some("bo"), "bod", "Tibetan"),
+    lang(Some("br"), "bre", "Breton"),
+    lang(Some("bs"), "bos", "Bosnian"),
+    lang(Some("ca"), "cat", "Catalan"),
+    lang(Some("ce"), "che", "Chechen"),
+    lang(None, "ceb", "Cebuano"),
+    lang(Some("ch"), "cha", "Chamorro"),
+    lang(Some("co"), "cos", "Corsican"),
+    lang(Some("cr"), "cre", "Cree"),
+    lang(Some("cs"), "ces", "Czech"),
+    lang(Some("cu"), "chu", "Church Slavic"),
+    lang(Some("cv"), "chv", "Chuvash"),
+    lang(Some("cy"), "cym", "Welsh"),
+    lang(Some("da"), "dan", "Danish"),
+    lang(Some("de"), "deu", "German"),
+    lang(Some("dv"), "div", "Dhivehi"),
+    lang(Some("dz"), "dzo", "Dzongkha"),
+    lang(Some("ee"), "ewe", "Ewe"),
+    lang(Some("el"), "ell", "Greek"),
+    lang(Some("en"), "eng", "English"),
+    lang(Some("eo"), "epo", "Esperanto"),
+    lang(Some("es"), "spa", "Spanish"),
+    lang(Some("et"), "est", "Estonian"),
+    lang(Some("eu"), "eus", "Basque"),
+    lang(Some("fa"), "fas", "Persian"),
+    lang(Some("ff"), "ful", "Fulah"),
+    lang(Some("fi"), "fin", "Finnish"),
+    lang(None, "fil", "Filipino"),
+    lang(Some("fj"), "fij", "Fijian"),
+    lang(Some("fo"), "fao", "Faroese"),
+    lang(Some("fr"), "fra", "French"),
+    lang(Some("fy"), "fry", "Western Frisian"),
+    lang(Some("ga"), "gle", "Irish"),
+    lang(Some("gd"), "gla", "Scottish Gaelic"),
+    lang(Some("gl"), "glg", "Galician"),
+    lang(Some("gn"), "grn", "Guarani"),
+    lang(Some("gu"), "guj", "Gujarati"),
+    lang(Some("gv"), "glv", "Manx"),
+    lang(Some("ha"), "hau", "Hausa"),
+    lang(Some("he"), "heb", "Hebrew"),
+    lang(Some("hi"), "hin", "Hindi"),
+    lang(Some("ho"), "hmo", "Hiri Motu"),
+    lang(Some("hr"), "hrv", "Croatian"),
+    lang(Some("ht"), "hat", "Haitian Creole"),
+    lang(Some("hu"), "hun", "Hungarian"),
+    lang(Some("hy"), "hye", "Armenian"),
+    lang(Some("hz"), "her", "Herero"),
+    lang(Some("ia"), "ina", "Interlingua"),
+    lang(Some("id"), "ind", "Indonesian"),
+    lang(Some("ie"), "ile", "Interlingue"),
+    lang(Some("ig"), "ibo", "Igbo"),
+    lang(Some("ii"), "iii", "Sichuan Yi"),
+    lang(Some("ik"), "ipk", "Inupiaq"),
+    lang(Some("io"), "ido", "Ido"),
+    lang(Some("is"), "isl", "Icelandic"),
+    lang(Some("it"), "ita", "Italian"),
+    lang(Some("iu"), "iku", "Inuktitut"),
+    lang(Some("ja"), "jpn", "Japanese"),
+    lang(Some("jv"), "jav", "Javanese"),
+    lang(Some("ka"), "kat", "Georgian"),
+    lang(None, "kea", "Kabuverdianu"),
+    lang(Some("kg"), "kon", "Kongo"),
+    lang(Some("ki"), "kik", "Kikuyu"),
+    lang(Some("kj"), "kua", "Kuanyama"),
+    lang(Some("kk"), "kaz", "Kazakh"),
+    lang(Some("kl"), "kal", "Kalaallisut"),
+    lang(Some("km"), "khm", "Khmer"),
+    lang(Some("kn"), "kan", "Kannada"),
+    lang(Some("ko"), "kor", "Korean"),
+    lang(Some("kr"), "kau", "Kanuri"),
+    lang(Some("ks"), "kas", "Kashmiri"),
+    lang(Some("ku"), "kur", "Kurdish"),
+    lang(Some("kv"), "kom", "Komi"),
+    lang(Some("kw"), "cor", "Cornish"),
+    lang(Some("ky"), "kir", "Kyrgyz"),
+    lang(Some("la"), "lat", "Latin"),
+    lang(Some("lb"), "ltz", "Luxembourgish"),
+    lang(Some("lg"), "lug", "Ganda"),
+    lang(Some("li"), "lim", "Limburgish"),
+    lang(Some("ln"), "lin", "Lingala"),
+    lang(Some("lo"), "lao", "Lao"),
+    lang(Some("lt"), "lit", "Lithuanian"),
+    lang(Some("lu"), "lub", "Luba-Katanga"),
+    lang(None, "luo", "Luo"),
+    lang(Some("lv"), "lav", "Latvian"),
+    lang(Some("mg"), "mlg", "Malagasy"),
+    lang(Some("mh"), "mah", "Marshallese"),
+    lang(Some("mi"), "mri", "Maori"),
+    lang(Some("mk"), "mkd", "Macedonian"),
+    lang(Some("ml"), "mal", "Malayalam"),
+    lang(Some("mn"), "mon", "Mongolian"),
+    lang(Some("mr"), "mar", "Marathi"),
+    lang(Some("ms"), "msa", "Malay"),
+    lang(Some("mt"), "mlt", "Maltese"),
+    lang(Some("my"), "mya", "Burmese"),
+    lang(Some("na"), "nau", "Nauru"),
+    lang(Some("nb"), "nob", "Norwegian Bokmål"),
+    lang(Some("nd"), "nde", "North Ndebele"),
+    lang(Some("ne"), "nep", "Nepali"),
+    lang(Some("ng"), "ndo", "Ndonga"),
+    lang(Some("nl"), "nld", "Dutch"),
+    lang(Some("nn"), "nno", "Norwegian Nynorsk"),
+    lang(Some("no"), "nor", "Norwegian"),
+    lang(Some("nr"), "nbl", "South Ndebele"),
+    lang(None, "nso", "Northern Sotho"),
+    lang(Some("nv"), "nav", "Navajo"),
+    lang(Some("ny"), "nya", "Chichewa"),
+    lang(Some("oc"), "oci", "Occitan"),
+    lang(Some("oj"), "oji", "Ojibwa"),
+    lang(Some("om"), "orm", "Oromo"),
+    lang(Some("or"), "ori", "Odia"),
+    lang(Some("os"), "oss", "Ossetian"),
+    lang(Some("pa"), "pan", "Punjabi"),
+    lang(Some("pi"), "pli", "Pali"),
+    lang(Some("pl"), "pol", "Polish"),
+    lang(Some("ps"), "pus", "Pashto"),
+    lang(Some("pt"), "por", "Portuguese"),
+    lang(Some("qu"), "que", "Quechua"),
+    lang(Some("rm"), "roh", "Romansh"),
+    lang(Some("rn"), "run", "Rundi"),
+    lang(Some("ro"), "ron", "Romanian"),
+    lang(Some("ru"), "rus", "Russian"),
+    lang(Some("rw"), "kin", "Kinyarwanda"),
+    lang(Some("sa"), "san", "Sanskrit"),
+    lang(Some("sc"), "srd", "Sardinian"),
+    lang(Some("sd"), "snd", "Sindhi"),
+    lang(Some("se"), "sme", "Northern Sami"),
+    lang(Some("sg"), "sag", "Sango"),
+    lang(Some("si"), "sin", "Sinhala"),
+    lang(Some("sk"), "slk", "Slovak"),
+    lang(Some("sl"), "slv", "Slovenian"),
+    lang(Some("sm"), "smo", "Samoan"),
+    lang(Some("sn"), "sna", "Shona"),
+    lang(Some("so"), "som", "Somali"),
+    lang(Some("sq"), "sqi", "Albanian"),
+    lang(Some("sr"), "srp", "Serbian"),
+    lang(Some("ss"), "ssw", "Swati"),
+    lang(Some("st"), "sot", "Southern Sotho"),
+    lang(Some("su"), "sun", "Sundanese"),
+    lang(Some("sv"), "swe", "Swedish"),
+    lang(Some("sw"), "swa", "Swahili"),
+    lang(Some("ta"), "tam", "Tamil"),
+    lang(Some("te"), "tel", "Telugu"),
+    lang(Some("tg"), "tgk", "Tajik"),
+    lang(Some("th"), "tha", "Thai"),
+    lang(Some("ti"), "tir", "Tigrinya"),
+    lang(Some("tk"), "tuk", "Turkmen"),
+    lang(Some("tl"), "tgl", "Tagalog"),
+    lang(Some("tn"), "tsn", "Tswana"),
+    lang(Some("to"), "ton", "Tonga"),
+    lang(Some("tr"), "tur", "Turkish"),
+    lang(Some("ts"), "tso", "Tsonga"),
+    lang(Some("tt"), "tat", "Tatar"),
+    lang(Some("tw"), "twi", "Twi"),
+    lang(Some("ty"), "tah", "Tahitian"),
+    lang(Some("ug"), "uig", "Uyghur"),
+    lang(Some("uk"), "ukr", "Ukrainian"),
+    lang(None, "umb", "Umbundu"),
+    lang(Some("ur"), "urd", "Urdu"),
+    lang(Some("uz"), "uzb", "Uzbek"),
+    lang(Some("ve"), "ven", "Venda"),
+    lang(Some("vi"), "vie", "Vietnamese"),
+    lang(Some("vo"), "vol", "Volapük"),
+    lang(Some("wa"), "wln", "Walloon"),
+    lang(Some("wo"), "wol", "Wolof"),
+    lang(Some("xh"), "xho", "Xhosa"),
+    lang(Some("yi"), "yid", "Yiddish"),
+    lang(Some("yo"), "yor", "Yoruba"),
+    lang(None, "yue", "Cantonese"),
+    lang(Some("za"), "zha", "Zhuang"),
+    lang(Some("zh"), "zho", "Chinese"),
+    lang(Some("zu"), "zul", "Zulu"),
+];
+
+/// ISO 639-2/B bibliographic codes mapped to their ISO 639-3 equivalent
+const BIBLIOGRAPHIC_ALIASES: &[(&str, &str)] = &[
+    ("alb", "sqi"),
+    ("arm", "hye"),
+    ("baq", "eus"),
+    ("bur", "mya"),
+    ("chi", "zho"),
+    ("cze", "ces"),
+    ("dut", "nld"),
+    ("fre", "fra"),
+    ("geo", "kat"),
+    ("ger", "deu"),
+    ("gre", "ell"),
+    ("ice", "isl"),
+    ("mac", "mkd"),
+    ("mao", "mri"),
+    ("may", "msa"),
+    ("per", "fas"),
+    ("rum", "ron"),
+    ("slo", "slk"),
+    ("tib", "bod"),
+    ("wel", "cym"),
+];
+
+/// An ISO 639 language code, normalized to its ISO 639-3 form.
+///
+/// Accepts ISO 639-1 (`"en"`) and ISO 639-3 (`"eng"`) codes, so a requested
+/// language compares equal to the 3-letter code detected by the API.
+/// Well-formed 3-letter codes missing from [`LANGUAGES`] are kept as-is.
+///
+/// Parsing is strict, but decoding never fails: codes in API responses that cannot be
+/// parsed (e.g. `"zh-Hans"`) are kept verbatim as unknown codes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LanguageCode {
+    code: String,
+    language: Option<&'static Language>,
+}
+
+impl LanguageCode {
+    /// Parse and normalize an ISO 639-1 or ISO 639-3 code
+    pub fn parse(code: &str) -> Result<Self, String> {
+        let normalized = code.trim().to_ascii_lowercase();
+
+        if !normalized.chars().all(|c| c.is_ascii_lowercase()) {
+            return Err(format!("Invalid language_code '{}'", code));
+        }
+
+        match normalized.len() {
+            2 => LANGUAGES
+                .iter()
+                .find(|language| language.iso639_1 == Some(normalized.as_str()))
+                .map(Self::from_language)
+                .ok_or_else(|| format!("Unknown ISO 639-1 language_code '{}'", code)),
+            3 => {
+                let iso639_3 = BIBLIOGRAPHIC_ALIASES
+                    .iter()
+                    .find(|(alias, _)| *alias == normalized)
+                    .map(|(_, iso639_3)| *iso639_3)
+                    .unwrap_or(normalized.as_str());
+
+                Ok(LANGUAGES
+                    .iter()
+                    .find(|language| language.iso639_3 == iso639_3)
+                    .map(Self::from_language)
+                    .unwrap_or(Self {
+                        code: normalized,
+                        language: None,
+                    }))
+            }
+            _ => Err(format!(
+                "Invalid language_code '{}': expected an ISO 639-1 or ISO 639-3 code",
+                code
+            )),
+        }
+    }
+
+    /// Parse `code`, keeping it verbatim as an unknown code when it is not valid
+    fn parse_lenient(code: &str) -> Self {
+        Self::parse(code).unwrap_or_else(|_| Self {
+            code: code.trim().to_string(),
+            language: None,
+        })
+    }
+
+    fn from_language(language: &'static Language) -> Self {
+        Self {
+            code: language.iso639_3.to_string(),
+            language: Some(language),
+        }
+    }
+
+    /// Two-letter ISO 639-1 code, if the language has one
+    pub fn iso639_1(&self) -> Option<&'static str> {
+        self.language.and_then(|language| language.iso639_1)
+    }
+
+    /// Three-letter ISO 639-3 code, or the code as received for unparseable API values
+    pub fn iso639_3(&self) -> &str {
+        &self.code
+    }
+
+    /// English name of the language, if known
+    pub fn english_name(&self) -> Option<&'static str> {
+        self.language.map(|language| language.english_name)
+    }
+
+    /// Whether the code was found in [`LANGUAGES`]
+    pub fn is_known(&self) -> bool {
+        self.language.is_some()
+    }
+}
+
+impl fmt::Display for LanguageCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.code)
+    }
+}
+
+impl FromStr for LanguageCode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LanguageCode::parse(s)
+    }
+}
+
+impl AsRef<str> for LanguageCode {
+    fn as_ref(&self) -> &str {
+        &self.code
+    }
+}
+
+impl Serialize for LanguageCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.code)
+    }
+}
+
+impl<'de> Deserialize<'de> for LanguageCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Ok(LanguageCode::parse_lenient(&code))
+    }
+}
+
+/// Decode an optional language code, treating an empty string like a missing one
+pub(crate) fn deserialize_optional<'de, D>(
+    deserializer: D,
+) -> Result<Option<LanguageCode>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let code = Option::<String>::deserialize(deserializer)?;
+    Ok(code
+        .filter(|code| !code.trim().is_empty())
+        .map(|code| LanguageCode::parse_lenient(&code)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalization() {
+        let en = LanguageCode::parse("en").unwrap();
+        let eng = LanguageCode::parse("ENG").unwrap();
+
+        assert_eq!(en, eng);
+        assert_eq!(en.iso639_3(), "eng");
+        assert_eq!(en.iso639_1(), Some("en"));
+        assert_eq!(en.english_name(), Some("English"));
+        assert_eq!(LanguageCode::parse("ger").unwrap().iso639_1(), Some("de"));
+
+        let cantonese = LanguageCode::parse("yue").unwrap();
+        assert_eq!(cantonese.iso639_1(), None);
+        assert_eq!(cantonese.english_name(), Some("Cantonese"));
+
+        let unknown = LanguageCode::parse("tzm").unwrap();
+        assert!(!unknown.is_known());
+        assert_eq!(unknown.to_string(), "tzm");
+    }
+
+    #[test]
+    fn test_invalid_codes() {
+        assert!(LanguageCode::parse("xx").is_err());
+        assert!(LanguageCode::parse("en-US").is_err());
+        assert!(LanguageCode::parse("english").is_err());
+        assert!(LanguageCode::parse("").is_err());
+    }
+
+    #[test]
+    fn test_lenient_decoding() {
+        let zh: LanguageCode = serde_json::from_str(r#""zh-Hans""#).unwrap();
+        assert!(!zh.is_known());
+        assert_eq!(zh.to_string(), "zh-Hans");
+
+        let xx: LanguageCode = serde_json::from_str(r#""xx""#).unwrap();
+        assert!(!xx.is_known());
+        assert_eq!(serde_json::to_string(&xx).unwrap(), r#""xx""#);
+
+        let eng: LanguageCode = serde_json::from_str(r#""en""#).unwrap();
+        assert_eq!(eng.iso639_3(), "eng");
+    }
+
+    #[test]
+    fn test_language_table_is_consistent() {
+        for (i, language) in LANGUAGES.iter().enumerate() {
+            assert_eq!(language.iso639_3.len(), 3);
+            assert!(
+                LANGUAGES[i + 1..]
+                    .iter()
+                    .all(|other| other.iso639_3 != language.iso639_3
+                        && (language.iso639_1.is_none() || other.iso639_1 != language.iso639_1))
+            );
+        }
+    }
+}
@@ -22,13 +22,48 @@
 //! ```
 
 use reqwest::Client;
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncRead;
+use tokio::sync::watch;
 
+pub mod alignment;
+pub mod audio;
 pub mod error;
+pub mod estimate;
+pub mod formats;
+pub mod language;
 pub mod models;
+pub mod poll;
+pub mod retry;
+pub mod subtitles;
 pub mod types;
+mod upload;
+mod validation;
+pub mod webhook;
 
+pub use alignment::{ForcedAlignmentBuilder, ForcedAlignmentResponse, ForcedAlignmentWord};
+pub use audio::AudioFormat;
 pub use error::ElevenLabsSTTError;
+pub use estimate::{CostEstimate, Pricing};
+pub use formats::{AdditionalFormat, ExportOptions, ExportedFormat, TextExportOptions};
+pub use language::LanguageCode;
+pub use models::{Model, ModelCapabilities};
+pub use poll::PollOptions;
+pub use retry::RetryPolicy;
+pub use subtitles::{SubtitleOptions, WebVttOptions};
+pub use tokio_util::sync::CancellationToken;
 pub use types::*;
+pub use upload::{ProgressCallback, UploadProgress};
+use upload::{UploadControl, UploadSource};
+pub use webhook::{WebhookEvent, WebhookVerifier};
+
+/// Default ElevenLabs API base URL
+pub const DEFAULT_BASE_URL: &str = "https://api.elevenlabs.io/v1";
 
 /// Main client for interacting with ElevenLabs API
 #[derive(Clone)]
@@ -36,6 +71,7 @@ pub struct ElevenLabsSTTClient {
     client: Client,
     api_key: String,
     base_url: String,
+    retry_policy: RetryPolicy,
 }
 
 impl ElevenLabsSTTClient {
@@ -44,45 +80,347 @@ impl ElevenLabsSTTClient {
         Self {
             client: Client::new(),
             api_key: api_key.into(),
-            base_url: "https://api.elevenlabs.io/v1".to_string(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            retry_policy: RetryPolicy::default(),
         }
     }
 
+    /// Start building a client with a configurable HTTP transport
+    pub fn builder() -> ElevenLabsSTTClientBuilder {
+        ElevenLabsSTTClientBuilder::new()
+    }
+
     /// Create a new client with custom base URL (for testing/enterprise)
     pub fn with_base_url<S: Into<String>>(api_key: S, base_url: S) -> Self {
         Self {
             client: Client::new(),
             api_key: api_key.into(),
             base_url: base_url.into(),
+            retry_policy: RetryPolicy::default(),
         }
     }
 
+    /// Replace the retry policy used for every request
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
     /// Start building a speech-to-text request
     pub fn speech_to_text<F: Into<Option<Vec<u8>>>>(&self, file: F) -> SpeechToTextBuilder {
         SpeechToTextBuilder::new(self.clone(), file.into())
     }
 
-    /// Internal method to execute STT request
-    pub(crate) async fn execute_stt(
+    /// Start building a speech-to-text request that streams the file from disk
+    pub fn speech_to_text_file<P: Into<PathBuf>>(&self, path: P) -> SpeechToTextBuilder {
+        let mut builder = SpeechToTextBuilder::new(self.clone(), None);
+        builder.upload = Some(UploadSource::Path(path.into()));
+        builder
+    }
+
+    /// Start building a speech-to-text request that streams `length` bytes from a reader.
+    /// Requests built from a reader are sent once and never retried.
+    pub fn speech_to_text_reader<R>(&self, reader: R, length: u64) -> SpeechToTextBuilder
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        let mut builder = SpeechToTextBuilder::new(self.clone(), None);
+        builder.upload = Some(UploadSource::Reader {
+            reader: Some(Box::pin(reader)),
+            length,
+        });
+        builder
+    }
+
+    /// Start building a forced alignment request timing `text` against the audio
+    pub fn forced_alignment<S: Into<String>>(
         &self,
-        request: STTRequest,
+        audio: Vec<u8>,
+        text: S,
+    ) -> ForcedAlignmentBuilder {
+        ForcedAlignmentBuilder::new(self.clone(), UploadSource::Bytes(audio.into()), text.into())
+    }
+
+    /// Start building a forced alignment request that streams the audio from disk
+    pub fn forced_alignment_file<P: Into<PathBuf>, S: Into<String>>(
+        &self,
+        path: P,
+        text: S,
+    ) -> ForcedAlignmentBuilder {
+        ForcedAlignmentBuilder::new(self.clone(), UploadSource::Path(path.into()), text.into())
+    }
+
+    /// Fetch a previously requested transcript by its transcription ID
+    pub async fn get_transcript<S: AsRef<str>>(
+        &self,
+        transcription_id: S,
+    ) -> Result<STTResponse, ElevenLabsSTTError> {
+        let url = self.transcript_url(transcription_id.as_ref())?;
+
+        self.retry_policy
+            .run(|| async {
+                self.send_request(reqwest::Method::GET, url.clone())
+                    .await?
+                    .json::<STTResponse>()
+                    .await
+                    .map_err(ElevenLabsSTTError::ParseError)
+            })
+            .await
+    }
+
+    /// Delete a transcript by its transcription ID
+    pub async fn delete_transcript<S: AsRef<str>>(
+        &self,
+        transcription_id: S,
+    ) -> Result<(), ElevenLabsSTTError> {
+        let url = self.transcript_url(transcription_id.as_ref())?;
+
+        self.retry_policy
+            .run(|| async {
+                self.send_request(reqwest::Method::DELETE, url.clone())
+                    .await
+                    .map(|_| ())
+            })
+            .await
+    }
+
+    /// Poll until the transcript of a submitted request is available.
+    /// A 404 carrying an API error status means the transcription is still in progress (or the
+    /// ID is unknown, see [`PollOptions::timeout`]); any other error, including a 404 for an
+    /// unknown route or from a proxy, is returned.
+    pub async fn wait_for_transcript<S: AsRef<str>>(
+        &self,
+        transcription_id: S,
+        options: PollOptions,
     ) -> Result<STTResponse, ElevenLabsSTTError> {
-        let mut form = reqwest::multipart::Form::new().text("model_id", request.model_id);
+        let transcription_id = transcription_id.as_ref();
+        let timeout = options.timeout_duration();
 
-        if let Some(file_data) = request.file {
-            let part = reqwest::multipart::Part::bytes(file_data)
-                .file_name("file")
-                .mime_str("application/octet-stream")
-                .map_err(|e| ElevenLabsSTTError::RequestError(e));
+        let poll = async {
+            let mut poll = 1;
+            loop {
+                match self.poll_transcript(transcription_id).await {
+                    Ok(None) => {
+                        tokio::time::sleep(options.interval(poll)).await;
+                        poll += 1;
+                    }
+                    Ok(Some(transcript)) => return Ok(transcript),
+                    Err(error) => return Err(error),
+                }
+            }
+        };
+        let poll = async {
+            tokio::time::timeout(timeout, poll)
+                .await
+                .unwrap_or(Err(ElevenLabsSTTError::Timeout(timeout)))
+        };
 
-            match part {
-                Ok(part) => form = form.part("file", part),
-                Err(e) => return Err(e),
+        match options.cancellation() {
+            Some(token) => {
+                tokio::select! {
+                    biased;
+                    _ = token.cancelled() => Err(ElevenLabsSTTError::Cancelled),
+                    result = poll => result,
+                }
             }
+            None => poll.await,
+        }
+    }
+
+    /// Fetch a transcript, or `None` while the API answers 404 for it
+    async fn poll_transcript(
+        &self,
+        transcription_id: &str,
+    ) -> Result<Option<STTResponse>, ElevenLabsSTTError> {
+        let url = self.transcript_url(transcription_id)?;
+
+        self.retry_policy
+            .run(|| async {
+                let response = self
+                    .client
+                    .get(url.clone())
+                    .header("xi-api-key", &self.api_key)
+                    .send()
+                    .await?;
+
+                if response.status() == reqwest::StatusCode::NOT_FOUND {
+                    let body = response.text().await.unwrap_or_default();
+                    if error::is_api_error_body(&body) {
+                        return Ok(None);
+                    }
+                    return Err(ElevenLabsSTTError::from_status(404, None, &body));
+                }
+                if !response.status().is_success() {
+                    return Err(ElevenLabsSTTError::from_response(response).await);
+                }
+
+                response
+                    .json::<STTResponse>()
+                    .await
+                    .map(Some)
+                    .map_err(ElevenLabsSTTError::ParseError)
+            })
+            .await
+    }
+
+    /// Fetch the subscription of the account: tier, used and available credits, reset date
+    pub async fn subscription(&self) -> Result<Subscription, ElevenLabsSTTError> {
+        let url = self.endpoint_url("user/subscription")?;
+
+        self.retry_policy
+            .run(|| async {
+                self.send_request(reqwest::Method::GET, url.clone())
+                    .await?
+                    .json::<Subscription>()
+                    .await
+                    .map_err(ElevenLabsSTTError::ParseError)
+            })
+            .await
+    }
+
+    /// `{base_url}/{path}`
+    fn endpoint_url(&self, path: &str) -> Result<reqwest::Url, ElevenLabsSTTError> {
+        reqwest::Url::parse(&format!("{}/{}", self.base_url, path))
+            .map_err(|e| ElevenLabsSTTError::ValidationError(format!("Invalid base_url: {}", e)))
+    }
+
+    /// `{base_url}/speech-to-text/transcripts/{transcription_id}` with the ID percent-encoded
+    fn transcript_url(&self, transcription_id: &str) -> Result<reqwest::Url, ElevenLabsSTTError> {
+        if transcription_id.trim().is_empty() {
+            return Err(ElevenLabsSTTError::ValidationError(
+                "transcription_id: must not be empty".to_string(),
+            ));
+        }
+
+        let mut url = self.endpoint_url("speech-to-text/transcripts")?;
+        url.path_segments_mut()
+            .map_err(|_| {
+                ElevenLabsSTTError::ValidationError(
+                    "Invalid base_url: cannot be a base".to_string(),
+                )
+            })?
+            .push(transcription_id);
+
+        Ok(url)
+    }
+
+    /// Send a single authenticated request without a body, mapping error statuses
+    async fn send_request(
+        &self,
+        method: reqwest::Method,
+        url: reqwest::Url,
+    ) -> Result<reqwest::Response, ElevenLabsSTTError> {
+        let response = self
+            .client
+            .request(method, url)
+            .header("xi-api-key", &self.api_key)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(ElevenLabsSTTError::from_response(response).await);
+        }
+
+        Ok(response)
+    }
+
+    /// Internal method to execute STT request, retrying transient failures
+    pub(crate) async fn execute_stt<T: DeserializeOwned>(
+        &self,
+        request: STTRequest,
+        upload: Option<UploadSource>,
+        control: UploadControl,
+    ) -> Result<T, ElevenLabsSTTError> {
+        self.send_multipart("speech-to-text", upload, control, |file| {
+            Self::stt_form(&request, file)
+        })
+        .await
+    }
+
+    /// Upload a multipart form to `path`, retrying transient failures and honouring
+    /// the progress, cancellation and file type settings of `control`.
+    /// `form` builds the form of each attempt around a freshly read file part.
+    pub(crate) async fn send_multipart<T, F>(
+        &self,
+        path: &str,
+        mut upload: Option<UploadSource>,
+        control: UploadControl,
+        form: F,
+    ) -> Result<T, ElevenLabsSTTError>
+    where
+        T: DeserializeOwned,
+        F: Fn(
+            Option<reqwest::multipart::Part>,
+        ) -> Result<reqwest::multipart::Form, ElevenLabsSTTError>,
+    {
+        // A reader can only be streamed once, so it is never retried
+        let retry_policy = match &upload {
+            Some(source) if !source.is_replayable() => RetryPolicy::none(),
+            _ => self.retry_policy.clone(),
+        };
+        let url = &self.endpoint_url(path)?;
+        let form = &form;
+        let cancellation = control.cancellation.clone();
+        let control = &control;
+
+        let attempts = retry_policy.run_upload(|| {
+            let part = upload
+                .as_mut()
+                .map(|source| source.next_part(control))
+                .transpose();
+            async move { self.post_multipart(url, form(part?)?).await }
+        });
+
+        match cancellation {
+            Some(token) => tokio::select! {
+                biased;
+                _ = token.cancelled() => Err(ElevenLabsSTTError::Cancelled),
+                result = attempts => result,
+            },
+            None => attempts.await,
+        }
+    }
+
+    /// Send a single multipart request attempt
+    async fn post_multipart<T: DeserializeOwned>(
+        &self,
+        url: &reqwest::Url,
+        form: reqwest::multipart::Form,
+    ) -> Result<T, ElevenLabsSTTError> {
+        let response = self
+            .client
+            .post(url.clone())
+            .header("xi-api-key", &self.api_key)
+            .multipart(form)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(ElevenLabsSTTError::from_response(response).await);
+        }
+
+        response
+            .json::<T>()
+            .await
+            .map_err(ElevenLabsSTTError::ParseError)
+    }
+
+    /// Multipart form of an STT request
+    fn stt_form(
+        request: &STTRequest,
+        file: Option<reqwest::multipart::Part>,
+    ) -> Result<reqwest::multipart::Form, ElevenLabsSTTError> {
+        let mut form = reqwest::multipart::Form::new().text("model_id", request.model_id.clone());
+
+        if let Some(part) = file {
+            form = form.part("file", part);
         }
 
         let request_fields = vec![
-            ("language_code", request.language_code.map(|n| n)),
+            (
+                "language_code",
+                request.language_code.as_ref().map(|n| n.to_string()),
+            ),
             (
                 "tag_audio_events",
                 request.tag_audio_events.map(|n| n.to_string()),
@@ -90,26 +428,24 @@ impl ElevenLabsSTTClient {
             ("num_speakers", request.num_speakers.map(|n| n.to_string())),
             (
                 "timestamps_granularity",
-                request.timestamps_granularity.map(|n| n),
+                request.timestamps_granularity.map(|n| n.to_string()),
             ),
             ("diarize", request.diarize.map(|n| n.to_string())),
             (
                 "diarization_threshold",
                 request.diarization_threshold.map(|n| n.to_string()),
             ),
-            ("cloud_storage_url", request.cloud_storage_url.map(|n| n)),
+            ("cloud_storage_url", request.cloud_storage_url.clone()),
             ("webhook", request.webhook.map(|n| n.to_string())),
-            ("webhook_id", request.webhook_id.map(|n| n)),
+            ("webhook_id", request.webhook_id.clone()),
             ("temperature", request.temperature.map(|n| n.to_string())),
             ("seed", request.seed.map(|n| n.to_string())),
             (
                 "use_multi_channel",
                 request.use_multi_channel.map(|n| n.to_string()),
             ),
-            (
-                "webhook_metadata",
-                request.webhook_metadata.map(|n| n.to_string()),
-            ),
+            ("webhook_metadata", request.webhook_metadata.clone()),
+            ("file_format", request.file_format.map(|n| n.to_string())),
         ];
 
         for (key, value) in request_fields {
@@ -118,40 +454,204 @@ impl ElevenLabsSTTClient {
             }
         }
 
-        let url = format!("{}/speech-to-text", self.base_url);
-
-        let response = self
-            .client
-            .post(&url)
-            .header("xi-api-key", &self.api_key)
-            .multipart(form)
-            .send()
-            .await?;
+        if let Some(formats) = &request.additional_formats {
+            let formats = serde_json::to_string(formats).map_err(|e| {
+                ElevenLabsSTTError::ValidationError(format!(
+                    "additional_formats: failed to serialize: {}",
+                    e
+                ))
+            })?;
+            form = form.text("additional_formats", formats);
+        }
 
-        if !response.status().is_success() {
-            return Err(ElevenLabsSTTError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
+        // Lists are sent as one field per item
+        for keyterm in request.keyterms.iter().flatten() {
+            form = form.text("keyterms", keyterm.clone());
         }
 
-        let parse_response = response.json::<STTResponse>().await;
+        Ok(form)
+    }
+}
 
-        match parse_response {
-            Ok(stt_response) => return Ok(stt_response),
-            Err(e) => return Err(ElevenLabsSTTError::ParseError(e)),
+/// Builder for [`ElevenLabsSTTClient`] exposing the underlying HTTP transport settings
+pub struct ElevenLabsSTTClientBuilder {
+    api_key: Option<String>,
+    base_url: Option<String>,
+    http_client: Option<Client>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    user_agent: Option<String>,
+    default_headers: Option<HeaderMap>,
+    proxies: Vec<reqwest::Proxy>,
+    root_certificates: Vec<reqwest::Certificate>,
+    retry_policy: RetryPolicy,
+}
+
+impl ElevenLabsSTTClientBuilder {
+    fn new() -> Self {
+        Self {
+            api_key: None,
+            base_url: None,
+            http_client: None,
+            timeout: None,
+            connect_timeout: None,
+            read_timeout: None,
+            user_agent: None,
+            default_headers: None,
+            proxies: Vec::new(),
+            root_certificates: Vec::new(),
+            retry_policy: RetryPolicy::default(),
         }
     }
+
+    /// Set the API key to use (required)
+    pub fn api_key<S: Into<String>>(mut self, api_key: S) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Set the base URL to use (for testing/enterprise)
+    pub fn base_url<S: Into<String>>(mut self, base_url: S) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Use a pre-built `reqwest::Client`.
+    /// When set, the transport settings below are ignored.
+    pub fn http_client(mut self, http_client: Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// Set the total timeout for each request
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the timeout for establishing a connection
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Set the timeout for each read operation
+    pub fn read_timeout(mut self, read_timeout: Duration) -> Self {
+        self.read_timeout = Some(read_timeout);
+        self
+    }
+
+    /// Set the `User-Agent` header sent with every request
+    pub fn user_agent<S: Into<String>>(mut self, user_agent: S) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Set headers sent with every request
+    pub fn default_headers(mut self, default_headers: HeaderMap) -> Self {
+        self.default_headers = Some(default_headers);
+        self
+    }
+
+    /// Add a proxy to route requests through
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Add a trusted TLS root certificate
+    pub fn add_root_certificate(mut self, certificate: reqwest::Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Set the retry policy used for every request
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Build the client, validating the API key and base URL
+    pub fn build(self) -> Result<ElevenLabsSTTClient, ElevenLabsSTTError> {
+        let api_key = match self.api_key {
+            Some(api_key) if !api_key.trim().is_empty() => api_key,
+            _ => {
+                return Err(ElevenLabsSTTError::ValidationError(
+                    "api_key is required".to_string(),
+                ));
+            }
+        };
+
+        let base_url = validate_base_url(self.base_url.as_deref().unwrap_or(DEFAULT_BASE_URL))?;
+
+        let client = match self.http_client {
+            Some(client) => client,
+            None => {
+                let mut builder = Client::builder();
+
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(connect_timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(connect_timeout);
+                }
+                if let Some(read_timeout) = self.read_timeout {
+                    builder = builder.read_timeout(read_timeout);
+                }
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                if let Some(default_headers) = self.default_headers {
+                    builder = builder.default_headers(default_headers);
+                }
+                for proxy in self.proxies {
+                    builder = builder.proxy(proxy);
+                }
+                for certificate in self.root_certificates {
+                    builder = builder.add_root_certificate(certificate);
+                }
+
+                builder.build().map_err(ElevenLabsSTTError::RequestError)?
+            }
+        };
+
+        Ok(ElevenLabsSTTClient {
+            client,
+            api_key,
+            base_url,
+            retry_policy: self.retry_policy,
+        })
+    }
+}
+
+/// Check that the base URL is an absolute http(s) URL and strip any trailing slash
+fn validate_base_url(base_url: &str) -> Result<String, ElevenLabsSTTError> {
+    let url = reqwest::Url::parse(base_url).map_err(|e| {
+        ElevenLabsSTTError::ValidationError(format!("Invalid base_url '{}': {}", base_url, e))
+    })?;
+
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return Err(ElevenLabsSTTError::ValidationError(format!(
+            "Invalid base_url '{}': scheme must be http or https",
+            base_url
+        )));
+    }
+
+    Ok(base_url.trim_end_matches('/').to_string())
 }
 
 pub struct SpeechToTextBuilder {
     client: ElevenLabsSTTClient,
     file: Option<Vec<u8>>,
-    model_id: Option<String>,
-    language_code: Option<String>,
+    upload: Option<UploadSource>,
+    control: UploadControl,
+    deferred_error: Option<String>,
+    model_id: Option<Model>,
+    language_code: Option<LanguageCode>,
     tag_audio_events: Option<bool>,
     num_speakers: Option<u32>,
-    timestamps_granularity: Option<String>,
+    timestamps_granularity: Option<TimestampsGranularity>,
     diarize: Option<bool>,
     diarization_threshold: Option<f32>,
     cloud_storage_url: Option<String>,
@@ -161,6 +661,10 @@ pub struct SpeechToTextBuilder {
     seed: Option<u32>,
     webhook_metadata: Option<String>,
     use_multi_channel: Option<bool>,
+    file_format: Option<FileFormat>,
+    keyterms: Option<Vec<String>>,
+    additional_formats: Option<Vec<AdditionalFormat>>,
+    budget: Option<(Pricing, f64)>,
 }
 
 impl SpeechToTextBuilder {
@@ -168,6 +672,9 @@ impl SpeechToTextBuilder {
         Self {
             client,
             file,
+            upload: None,
+            control: UploadControl::default(),
+            deferred_error: None,
             model_id: None,
             language_code: None,
             tag_audio_events: None,
@@ -182,18 +689,26 @@ impl SpeechToTextBuilder {
             seed: None,
             use_multi_channel: None,
             webhook_metadata: None,
+            file_format: None,
+            keyterms: None,
+            additional_formats: None,
+            budget: None,
         }
     }
 
     /// Set the model to use
-    pub fn model<S: Into<String>>(mut self, model_id: S) -> Self {
-        self.model_id = Some(model_id.into());
+    pub fn model<M: Into<Model>>(mut self, model: M) -> Self {
+        self.model_id = Some(model.into());
         self
     }
 
-    /// Set the language code to use
-    pub fn language_code<S: Into<String>>(mut self, language_code: S) -> Self {
-        self.language_code = Some(language_code.into());
+    /// Set the language code to use (ISO 639-1 or ISO 639-3).
+    /// An invalid code is reported as a `ValidationError` by `execute()`.
+    pub fn language_code<S: AsRef<str>>(mut self, language_code: S) -> Self {
+        match LanguageCode::parse(language_code.as_ref()) {
+            Ok(language_code) => self.language_code = Some(language_code),
+            Err(message) => self.defer_error(message),
+        }
         self
     }
 
@@ -210,11 +725,28 @@ impl SpeechToTextBuilder {
     }
 
     /// Set the timestamps granularity to use
-    pub fn timestamps_granularity<S: Into<String>>(mut self, timestamps_granularity: S) -> Self {
-        self.timestamps_granularity = Some(timestamps_granularity.into());
+    pub fn timestamps_granularity(mut self, timestamps_granularity: TimestampsGranularity) -> Self {
+        self.timestamps_granularity = Some(timestamps_granularity);
         self
     }
 
+    /// Set the timestamps granularity from a string ("none", "word" or "character").
+    /// An invalid value is reported as a `ValidationError` by `execute()`.
+    pub fn timestamps_granularity_str<S: AsRef<str>>(mut self, timestamps_granularity: S) -> Self {
+        match timestamps_granularity.as_ref().parse() {
+            Ok(granularity) => self.timestamps_granularity = Some(granularity),
+            Err(message) => self.defer_error(message),
+        }
+        self
+    }
+
+    /// Keep the first error raised while configuring the builder, reported by `validate()`
+    fn defer_error(&mut self, message: String) {
+        if self.deferred_error.is_none() {
+            self.deferred_error = Some(message);
+        }
+    }
+
     /// Set the diarize to use
     pub fn diarize<B: Into<bool>>(mut self, diarize: B) -> Self {
         self.diarize = Some(diarize.into());
@@ -263,35 +795,240 @@ impl SpeechToTextBuilder {
         self
     }
 
+    /// Set the format of the uploaded audio, `FileFormat::PcmS16le16` for raw 16kHz mono PCM.
+    /// See [`audio::pcm_s16le_from_i16`] and [`audio::pcm_s16le_from_f32`] to build the bytes.
+    pub fn file_format(mut self, file_format: FileFormat) -> Self {
+        self.file_format = Some(file_format);
+        self
+    }
+
+    /// Set the keyterms (product names, jargon) to bias recognition towards.
+    /// At most 100 keyterms, each shorter than 50 characters and at most 5 words.
+    pub fn keyterms<I, S>(mut self, keyterms: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.keyterms = Some(keyterms.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Request an extra format (srt, txt, segmented_json, docx, pdf, html) rendered server-side,
+    /// returned in `STTResponse::additional_formats`
+    pub fn additional_format(mut self, format: AdditionalFormat) -> Self {
+        self.additional_formats
+            .get_or_insert_with(Vec::new)
+            .push(format);
+        self
+    }
+
+    /// Request several extra formats at once, see `additional_format`
+    pub fn additional_formats<I: IntoIterator<Item = AdditionalFormat>>(
+        mut self,
+        formats: I,
+    ) -> Self {
+        self.additional_formats
+            .get_or_insert_with(Vec::new)
+            .extend(formats);
+        self
+    }
+
     /// Set the webhook metadata to use
     pub fn webhook_metadata<S: Into<String>>(mut self, webhook_metadata: S) -> Self {
         self.webhook_metadata = Some(webhook_metadata.into());
         self
     }
 
-    /// Execute the speech-to-text request
+    /// Set the webhook metadata from any serializable value (struct, map, `serde_json::Value`).
+    /// It must serialize to a JSON object with a maximum depth of 2 levels and size of 16KB,
+    /// otherwise a `ValidationError` is reported by `execute()`.
+    pub fn webhook_metadata_json<T: Serialize + ?Sized>(mut self, webhook_metadata: &T) -> Self {
+        let metadata = serde_json::to_string(webhook_metadata)
+            .map_err(|e| format!("webhook_metadata: failed to serialize: {}", e))
+            .and_then(|metadata| {
+                validation::validate_webhook_metadata(&metadata).map(|_| metadata)
+            });
+
+        match metadata {
+            Ok(metadata) => self.webhook_metadata = Some(metadata),
+            Err(message) => self.defer_error(message),
+        }
+        self
+    }
+
+    /// Call `callback` with the number of bytes sent as the file is uploaded
+    pub fn on_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(UploadProgress) + Send + Sync + 'static,
+    {
+        self.control.progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Publish upload progress to a `watch` channel
+    pub fn progress_watch(self, sender: watch::Sender<UploadProgress>) -> Self {
+        self.on_progress(move |progress| {
+            sender.send_replace(progress);
+        })
+    }
+
+    /// Abort the request (including pending retries) when `token` is cancelled
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.control.cancellation = Some(token);
+        self
+    }
+
+    /// Refuse to send the request when its estimated cost exceeds `max_credits`.
+    /// Requests whose duration cannot be estimated are refused as well.
+    pub fn budget(mut self, pricing: Pricing, max_credits: f64) -> Self {
+        self.budget = Some((pricing, max_credits));
+        self
+    }
+
+    /// Estimate the audio duration and credit cost of the request.
+    /// In-memory WAV, MP3, FLAC and Ogg audio, WAV and FLAC files streamed from disk,
+    /// and raw PCM from any source can be inspected.
+    pub fn estimate(&self, pricing: &Pricing) -> Result<CostEstimate, ElevenLabsSTTError> {
+        let pcm = self.file_format == Some(FileFormat::PcmS16le16);
+        let duration = match (&self.upload, self.file.as_deref()) {
+            (Some(upload), _) if pcm => Some(audio::pcm_s16le_duration_of_len(upload.len()?)),
+            (None, Some(file)) if pcm => Some(audio::pcm_s16le_duration(file)),
+            (Some(upload), _) => match upload.header()? {
+                Some(header) => audio::header_duration(&header, upload.len()?),
+                None => {
+                    return Err(ElevenLabsSTTError::ValidationError(
+                        "file: duration cannot be estimated for audio streamed from a reader"
+                            .to_string(),
+                    ));
+                }
+            },
+            (None, Some(file)) => audio::duration(file),
+            (None, None) => {
+                return Err(ElevenLabsSTTError::ValidationError(
+                    "file: duration can only be estimated for uploaded audio".to_string(),
+                ));
+            }
+        };
+        let duration = duration.ok_or_else(|| {
+            let expected = match self.upload {
+                Some(_) => "expected WAV or FLAC for files streamed from disk",
+                None => "expected WAV, MP3, FLAC or Ogg",
+            };
+            ElevenLabsSTTError::ValidationError(format!(
+                "file: unable to read the audio duration, {}",
+                expected
+            ))
+        })?;
+        let model = self.selected_model();
+
+        Ok(CostEstimate {
+            credits: pricing.cost(&model, duration),
+            model,
+            duration,
+        })
+    }
+
+    /// Check the request against the API constraints without sending it
+    pub fn validate(&self) -> Result<(), ElevenLabsSTTError> {
+        if let Some(message) = &self.deferred_error {
+            return Err(ElevenLabsSTTError::ValidationError(message.clone()));
+        }
+
+        let file_size = match (&self.upload, &self.file) {
+            (Some(upload), _) => Some(upload.len()?),
+            (None, Some(file)) => Some(file.len() as u64),
+            (None, None) => None,
+        };
+
+        let header = match (&self.upload, &self.file) {
+            (Some(upload), _) => upload.header()?,
+            (None, Some(file)) => Some(file[..file.len().min(audio::SNIFF_LEN)].into()),
+            (None, None) => None,
+        };
+        // Raw PCM has no header to sniff
+        if let Some(header) = header
+            && self.file_format != Some(FileFormat::PcmS16le16)
+        {
+            validation::validate_media(&header)?;
+        }
+
+        validation::validate_request(&self.build_request(), &self.selected_model(), file_size)?;
+
+        if let Some((pricing, max_credits)) = &self.budget {
+            let estimate = self.estimate(pricing)?;
+            if estimate.credits > *max_credits {
+                return Err(ElevenLabsSTTError::BudgetExceeded {
+                    estimated_credits: estimate.credits,
+                    max_credits: *max_credits,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Execute the speech-to-text request and wait for the transcription.
+    /// Requests with `webhook(true)` return early without a transcript, use `submit()` instead.
     pub async fn execute(self) -> Result<STTResponse, ElevenLabsSTTError> {
-        let request = STTRequest {
-            file: self.file,
-            model_id: self
-                .model_id
-                .unwrap_or_else(|| models::elevanlabs_models::SCRIBE_V1.to_string()),
-            language_code: self.language_code,
+        if self.webhook == Some(true) {
+            return Err(ElevenLabsSTTError::ValidationError(
+                "webhook: requests with webhook=true return an acknowledgment, use submit()"
+                    .to_string(),
+            ));
+        }
+
+        self.send().await
+    }
+
+    /// Submit the request for asynchronous transcription delivered via webhook.
+    /// Sets `webhook` to true and returns the acknowledgment holding the transcription id.
+    pub async fn submit(mut self) -> Result<WebhookAcknowledgment, ElevenLabsSTTError> {
+        self.webhook = Some(true);
+        self.send().await
+    }
+
+    async fn send<T: DeserializeOwned>(self) -> Result<T, ElevenLabsSTTError> {
+        self.validate()?;
+
+        let request = self.build_request();
+        let upload = self
+            .upload
+            .or_else(|| self.file.map(|file| UploadSource::Bytes(file.into())));
+
+        let control = UploadControl {
+            raw_pcm: self.file_format == Some(FileFormat::PcmS16le16),
+            ..self.control
+        };
+
+        self.client.execute_stt(request, upload, control).await
+    }
+
+    fn selected_model(&self) -> Model {
+        self.model_id.clone().unwrap_or_default()
+    }
+
+    /// Request fields without the file, which is uploaded separately
+    fn build_request(&self) -> STTRequest {
+        STTRequest {
+            file: None,
+            model_id: self.selected_model().to_string(),
+            language_code: self.language_code.clone(),
             tag_audio_events: self.tag_audio_events,
             num_speakers: self.num_speakers,
             timestamps_granularity: self.timestamps_granularity,
             diarize: self.diarize,
             diarization_threshold: self.diarization_threshold,
-            cloud_storage_url: self.cloud_storage_url,
+            cloud_storage_url: self.cloud_storage_url.clone(),
             webhook: self.webhook,
-            webhook_id: self.webhook_id,
+            webhook_id: self.webhook_id.clone(),
             temperature: self.temperature,
             seed: self.seed,
             use_multi_channel: self.use_multi_channel,
-            webhook_metadata: self.webhook_metadata,
-        };
-
-        self.client.execute_stt(request).await
+            webhook_metadata: self.webhook_metadata.clone(),
+            file_format: self.file_format,
+            keyterms: self.keyterms.clone(),
+            additional_formats: self.additional_formats.clone(),
+        }
     }
 }
 
@@ -305,6 +1042,47 @@ mod tests {
         assert_eq!(client.api_key, "test-key");
     }
 
+    #[test]
+    fn test_client_builder() {
+        let client = ElevenLabsSTTClient::builder()
+            .api_key("test-key")
+            .base_url("https://example.com/v1/")
+            .timeout(Duration::from_secs(30))
+            .user_agent("elevenlabs_stt-tests")
+            .build()
+            .unwrap();
+
+        assert_eq!(client.api_key, "test-key");
+        assert_eq!(client.base_url, "https://example.com/v1");
+    }
+
+    #[test]
+    fn test_client_builder_validation() {
+        let missing_key = ElevenLabsSTTClient::builder().build();
+        assert!(matches!(
+            missing_key,
+            Err(ElevenLabsSTTError::ValidationError(_))
+        ));
+
+        let bad_url = ElevenLabsSTTClient::builder()
+            .api_key("test-key")
+            .base_url("not a url")
+            .build();
+        assert!(matches!(
+            bad_url,
+            Err(ElevenLabsSTTError::ValidationError(_))
+        ));
+
+        let bad_scheme = ElevenLabsSTTClient::builder()
+            .api_key("test-key")
+            .base_url("ftp://example.com")
+            .build();
+        assert!(matches!(
+            bad_scheme,
+            Err(ElevenLabsSTTError::ValidationError(_))
+        ));
+    }
+
     #[test]
     fn test_builder_pattern() {
         let client = ElevenLabsSTTClient::new("test-key");
@@ -314,9 +1092,243 @@ mod tests {
 
         // Builder pattern works
         assert_eq!(builder.file, None);
+        assert_eq!(builder.model_id, Some(Model::ScribeV1));
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_request() {
+        let client = ElevenLabsSTTClient::with_base_url("test-key", "http://127.0.0.1:9");
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let result = client
+            .speech_to_text(vec![0u8; 16])
+            .on_progress(|_| {})
+            .cancellation_token(token)
+            .execute()
+            .await;
+
+        assert!(matches!(result, Err(ElevenLabsSTTError::Cancelled)));
+    }
+
+    #[tokio::test]
+    async fn test_timestamps_granularity() {
+        let client = ElevenLabsSTTClient::new("test-key");
+
+        let builder = client
+            .speech_to_text(None)
+            .timestamps_granularity_str("Character");
+        assert_eq!(
+            builder.timestamps_granularity,
+            Some(TimestampsGranularity::Character)
+        );
+
+        let result = client
+            .speech_to_text(None)
+            .timestamps_granularity_str("words")
+            .execute()
+            .await;
+        assert!(matches!(
+            result,
+            Err(ElevenLabsSTTError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_model_conversion() {
+        assert_eq!(
+            Model::from("scribe_v1_experimental"),
+            Model::ScribeV1Experimental
+        );
+        assert_eq!(
+            Model::from("scribe_v9"),
+            Model::Custom("scribe_v9".to_string())
+        );
+        assert_eq!(Model::ScribeV1.to_string(), "scribe_v1");
+        assert_eq!(
+            serde_json::to_string(&Model::Custom("x".to_string())).unwrap(),
+            "\"x\""
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let client = ElevenLabsSTTClient::new("test-key");
+
+        assert!(client.speech_to_text(vec![0u8; 16]).validate().is_ok());
+
+        let html = b"<!DOCTYPE html><html>Not Found</html>".to_vec();
+        assert!(matches!(
+            client.speech_to_text(html).validate(),
+            Err(ElevenLabsSTTError::ValidationError(_))
+        ));
+        assert!(
+            client
+                .speech_to_text_file("inputs/speech.mp3")
+                .validate()
+                .is_ok()
+        );
+
+        match client
+            .speech_to_text(vec![0u8; 16])
+            .temperature(3.0)
+            .validate()
+        {
+            Err(ElevenLabsSTTError::ValidationError(message)) => {
+                assert!(message.starts_with("temperature"))
+            }
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_language_code() {
+        let client = ElevenLabsSTTClient::new("test-key");
+
+        let builder = client.speech_to_text(vec![0u8; 16]).language_code("fr");
+        assert_eq!(builder.language_code, Some("fra".parse().unwrap()));
+
+        let builder = client.speech_to_text(vec![0u8; 16]).language_code("french");
+        assert!(matches!(
+            builder.validate(),
+            Err(ElevenLabsSTTError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_webhook_metadata_json() {
+        #[derive(Serialize)]
+        struct Job {
+            job_id: u32,
+            tenant: &'static str,
+        }
+
+        let client = ElevenLabsSTTClient::new("test-key");
+
+        let builder = client
+            .speech_to_text(vec![0u8; 16])
+            .webhook(true)
+            .webhook_metadata_json(&Job {
+                job_id: 7,
+                tenant: "acme",
+            });
         assert_eq!(
-            builder.model_id,
-            Some(models::elevanlabs_models::SCRIBE_V1.to_string())
+            builder.webhook_metadata.as_deref(),
+            Some(r#"{"job_id":7,"tenant":"acme"}"#)
         );
+        assert!(builder.validate().is_ok());
+
+        let builder = client
+            .speech_to_text(vec![0u8; 16])
+            .webhook_metadata_json(&serde_json::json!({"a": {"b": {"c": 1}}}));
+        assert!(matches!(
+            builder.validate(),
+            Err(ElevenLabsSTTError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_execute_rejects_webhook_requests() {
+        let client = ElevenLabsSTTClient::new("test-key");
+
+        let result = client
+            .speech_to_text(vec![0u8; 16])
+            .webhook(true)
+            .execute()
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ElevenLabsSTTError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_estimate_and_budget() {
+        let client = ElevenLabsSTTClient::new("test-key");
+        let pricing = Pricing::per_hour(3600.0);
+
+        // 8kHz 8-bit mono WAV with 10 seconds of audio
+        let mut wav = b"RIFF\0\0\0\0WAVEfmt \x10\0\0\0\x01\0\x01\0".to_vec();
+        wav.extend_from_slice(&8000u32.to_le_bytes());
+        wav.extend_from_slice(&8000u32.to_le_bytes());
+        wav.extend_from_slice(b"\x01\0\x08\0data");
+        wav.extend_from_slice(&80_000u32.to_le_bytes());
+        wav.resize(wav.len() + 80_000, 0x80);
+
+        let estimate = client
+            .speech_to_text(wav.clone())
+            .estimate(&pricing)
+            .unwrap();
+        assert_eq!(estimate.duration, Duration::from_secs(10));
+        assert_eq!(estimate.credits, 10.0);
+
+        let within = client
+            .speech_to_text(wav.clone())
+            .budget(pricing.clone(), 20.0);
+        assert!(within.validate().is_ok());
+
+        let over = client.speech_to_text(wav).budget(pricing.clone(), 5.0);
+        assert!(matches!(
+            over.validate(),
+            Err(ElevenLabsSTTError::BudgetExceeded { .. })
+        ));
+
+        let pcm = client
+            .speech_to_text(audio::pcm_s16le_from_i16(&[0; 32_000]))
+            .file_format(FileFormat::PcmS16le16);
+        assert!(pcm.validate().is_ok());
+        assert_eq!(
+            pcm.estimate(&pricing).unwrap().duration,
+            Duration::from_secs(2)
+        );
+
+        let unknown = client
+            .speech_to_text(vec![0u8; 16])
+            .budget(pricing.clone(), 5.0);
+        assert!(matches!(
+            unknown.validate(),
+            Err(ElevenLabsSTTError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_estimate_file_on_disk() {
+        let client = ElevenLabsSTTClient::new("test-key");
+        let pricing = Pricing::per_hour(3600.0);
+
+        // 16kHz 16-bit mono WAV with 3 seconds of audio
+        let mut wav = b"RIFF\0\0\0\0WAVEfmt \x10\0\0\0\x01\0\x01\0".to_vec();
+        wav.extend_from_slice(&16_000u32.to_le_bytes());
+        wav.extend_from_slice(&32_000u32.to_le_bytes());
+        wav.extend_from_slice(b"\x02\0\x10\0data");
+        wav.extend_from_slice(&96_000u32.to_le_bytes());
+        wav.resize(wav.len() + 96_000, 0);
+
+        let path = std::env::temp_dir().join(format!("estimate-{}.wav", std::process::id()));
+        std::fs::write(&path, &wav).unwrap();
+
+        let file = client.speech_to_text_file(&path);
+        let estimate = file.estimate(&pricing);
+        let over = client
+            .speech_to_text_file(&path)
+            .budget(pricing.clone(), 2.0);
+        let over = over.validate();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(estimate.unwrap().duration, Duration::from_secs(3));
+        assert!(matches!(
+            over,
+            Err(ElevenLabsSTTError::BudgetExceeded { .. })
+        ));
+
+        // MP3 files would have to be read whole, so they are refused
+        let mp3 = client
+            .speech_to_text_file("inputs/speech.mp3")
+            .budget(pricing, 1000.0);
+        assert!(matches!(
+            mp3.validate(),
+            Err(ElevenLabsSTTError::ValidationError(_))
+        ));
     }
 }
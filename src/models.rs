@@ -1,5 +1,106 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
 /// Elevanlabs scribe common model IDs as constants for convenience
 pub mod elevanlabs_models {
     pub const SCRIBE_V1: &str = "scribe_v1";
     pub const SCRIBE_V1_EXPERIMENTAL: &str = "scribe_v1_experimental";
+    pub const SCRIBE_V2: &str = "scribe_v2";
+}
+
+/// Largest file accepted by the scribe models (3.0GB)
+pub const MAX_FILE_SIZE: u64 = 3 * 1024 * 1024 * 1024;
+
+/// Speech-to-text model used for transcription
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Model {
+    /// `scribe_v1`, the default model
+    #[default]
+    ScribeV1,
+    /// `scribe_v1_experimental`
+    ScribeV1Experimental,
+    /// `scribe_v2`, adds keyterm prompting
+    ScribeV2,
+    /// Any other model ID, sent as-is and not checked against known capabilities
+    Custom(String),
+}
+
+/// What a model supports, consulted before a request is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelCapabilities {
+    /// Whether `language_code` can be used to enforce the transcription language
+    pub supports_language_enforcement: bool,
+    /// Largest accepted upload in bytes
+    pub max_file_size: u64,
+    /// Whether `use_multi_channel` is supported
+    pub supports_multichannel: bool,
+    /// Whether `keyterms` can be used to bias recognition
+    pub supports_keyterms: bool,
+}
+
+impl Model {
+    /// Model ID sent to the API
+    pub fn as_str(&self) -> &str {
+        match self {
+            Model::ScribeV1 => elevanlabs_models::SCRIBE_V1,
+            Model::ScribeV1Experimental => elevanlabs_models::SCRIBE_V1_EXPERIMENTAL,
+            Model::ScribeV2 => elevanlabs_models::SCRIBE_V2,
+            Model::Custom(model_id) => model_id,
+        }
+    }
+
+    /// Capabilities of the model. Every scribe model enforces languages and handles
+    /// multichannel audio; keyterms need `scribe_v2`. Custom models are assumed to support everything.
+    pub fn capabilities(&self) -> ModelCapabilities {
+        let all = ModelCapabilities {
+            supports_language_enforcement: true,
+            max_file_size: MAX_FILE_SIZE,
+            supports_multichannel: true,
+            supports_keyterms: true,
+        };
+
+        match self {
+            Model::ScribeV1 | Model::ScribeV1Experimental => ModelCapabilities {
+                supports_keyterms: false,
+                ..all
+            },
+            Model::ScribeV2 | Model::Custom(_) => all,
+        }
+    }
+}
+
+impl fmt::Display for Model {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for Model {
+    fn from(model_id: &str) -> Self {
+        match model_id {
+            elevanlabs_models::SCRIBE_V1 => Model::ScribeV1,
+            elevanlabs_models::SCRIBE_V1_EXPERIMENTAL => Model::ScribeV1Experimental,
+            elevanlabs_models::SCRIBE_V2 => Model::ScribeV2,
+            other => Model::Custom(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for Model {
+    fn from(model_id: String) -> Self {
+        Model::from(model_id.as_str())
+    }
+}
+
+impl Serialize for Model {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Model {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Model::from)
+    }
 }
@@ -0,0 +1,100 @@
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// How [`ElevenLabsSTTClient::wait_for_transcript`](crate::ElevenLabsSTTClient::wait_for_transcript)
+/// polls for a submitted transcription.
+///
+/// The interval between polls grows from `initial_interval` by `multiplier`
+/// up to `max_interval`, until the transcript exists or `timeout` elapses.
+#[derive(Debug, Clone)]
+pub struct PollOptions {
+    initial_interval: Duration,
+    max_interval: Duration,
+    multiplier: f64,
+    timeout: Duration,
+    cancellation: Option<CancellationToken>,
+}
+
+impl PollOptions {
+    /// Create the default options: 1s initial interval, 15s cap, 10 minute timeout
+    pub fn new() -> Self {
+        Self {
+            initial_interval: Duration::from_secs(1),
+            max_interval: Duration::from_secs(15),
+            multiplier: 1.5,
+            timeout: Duration::from_secs(10 * 60),
+            cancellation: None,
+        }
+    }
+
+    /// Set the delay before the second poll
+    pub fn initial_interval(mut self, initial_interval: Duration) -> Self {
+        self.initial_interval = initial_interval;
+        self
+    }
+
+    /// Set the upper bound for the delay between two polls
+    pub fn max_interval(mut self, max_interval: Duration) -> Self {
+        self.max_interval = max_interval;
+        self
+    }
+
+    /// Set the factor applied to the interval after each poll
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Set how long to wait in total before giving up with a timeout error.
+    /// The API answers 404 both while a transcription is in progress and for an unknown ID,
+    /// so a mistyped ID keeps polling until this timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Stop polling when the token is cancelled
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    pub(crate) fn timeout_duration(&self) -> Duration {
+        self.timeout
+    }
+
+    pub(crate) fn cancellation(&self) -> Option<&CancellationToken> {
+        self.cancellation.as_ref()
+    }
+
+    /// Delay after the given 1-based poll
+    pub(crate) fn interval(&self, poll: u32) -> Duration {
+        let exponent = poll.saturating_sub(1).min(32) as i32;
+        let interval = self.initial_interval.as_secs_f64() * self.multiplier.powi(exponent);
+        Duration::from_secs_f64(interval.min(self.max_interval.as_secs_f64()))
+    }
+}
+
+impl Default for PollOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval_backoff() {
+        let options = PollOptions::new()
+            .initial_interval(Duration::from_millis(100))
+            .max_interval(Duration::from_millis(250))
+            .multiplier(2.0);
+
+        assert_eq!(options.interval(1), Duration::from_millis(100));
+        assert_eq!(options.interval(2), Duration::from_millis(200));
+        assert_eq!(options.interval(3), Duration::from_millis(250));
+        assert_eq!(options.interval(50), Duration::from_millis(250));
+    }
+}
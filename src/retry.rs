@@ -0,0 +1,296 @@
+use crate::error::ElevenLabsSTTError;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Decides whether a failed request should be retried
+pub type RetryPredicate = Arc<dyn Fn(&ElevenLabsSTTError) -> bool + Send + Sync>;
+
+/// Retry policy applied by the client to every API call.
+///
+/// Delays grow exponentially from `initial_backoff` up to `max_backoff`,
+/// with optional full jitter. A `Retry-After` value carried by the error
+/// takes precedence over the computed delay; when it is longer than
+/// `max_backoff` the error is returned instead of waiting.
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+    jitter: bool,
+    respect_retry_after: bool,
+    retry_uncertain_uploads: bool,
+    retryable: RetryPredicate,
+}
+
+impl RetryPolicy {
+    /// Create the default policy: 3 attempts, 500ms initial backoff, 30s cap, jitter enabled.
+    ///
+    /// Transcription uploads that time out or fail mid-body are not retried by default,
+    /// since the API may already be processing (and billing) them.
+    /// See [`RetryPolicy::retry_uncertain_uploads`].
+    pub fn new() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: true,
+            respect_retry_after: true,
+            retry_uncertain_uploads: false,
+            retryable: Arc::new(ElevenLabsSTTError::is_retryable),
+        }
+    }
+
+    /// A policy that never retries
+    pub fn none() -> Self {
+        Self::new().max_attempts(1)
+    }
+
+    /// Set the total number of attempts, including the first one (minimum 1)
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set the delay before the first retry
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Set the upper bound for any single delay
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Set the factor applied to the delay after each attempt
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Enable or disable random jitter on computed delays
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Whether a `Retry-After` value returned by the API overrides the computed delay
+    pub fn respect_retry_after(mut self, respect_retry_after: bool) -> Self {
+        self.respect_retry_after = respect_retry_after;
+        self
+    }
+
+    /// Also retry transcription uploads that failed in a way where the API may have received
+    /// them ([`ElevenLabsSTTError::may_have_been_processed`]). This risks transcribing,
+    /// and paying for, the same file twice. Disabled by default.
+    pub fn retry_uncertain_uploads(mut self, retry_uncertain_uploads: bool) -> Self {
+        self.retry_uncertain_uploads = retry_uncertain_uploads;
+        self
+    }
+
+    /// Replace the predicate deciding which errors are retryable.
+    /// Defaults to [`ElevenLabsSTTError::is_retryable`].
+    pub fn retry_if<F>(mut self, retryable: F) -> Self
+    where
+        F: Fn(&ElevenLabsSTTError) -> bool + Send + Sync + 'static,
+    {
+        self.retryable = Arc::new(retryable);
+        self
+    }
+
+    /// Delay to wait before the next attempt, or `None` if the error should be returned.
+    /// `attempt` is the 1-based number of the attempt that just failed.
+    /// A `Retry-After` longer than `max_backoff` gives up rather than sleeping that long.
+    pub fn next_delay(&self, attempt: u32, error: &ElevenLabsSTTError) -> Option<Duration> {
+        if attempt >= self.max_attempts || !(self.retryable)(error) {
+            return None;
+        }
+
+        if self.respect_retry_after
+            && let Some(retry_after) = error.retry_after()
+        {
+            return (retry_after <= self.max_backoff).then_some(retry_after);
+        }
+
+        Some(self.backoff(attempt))
+    }
+
+    /// Exponential backoff for the given 1-based attempt number
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(32) as i32;
+        let base = self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent);
+        let capped = base.min(self.max_backoff.as_secs_f64());
+
+        if self.jitter {
+            Duration::from_secs_f64(capped * fastrand::f64())
+        } else {
+            Duration::from_secs_f64(capped)
+        }
+    }
+
+    /// Run `operation` until it succeeds, the error is not retryable, or attempts run out
+    pub(crate) async fn run<T, F, Fut>(&self, operation: F) -> Result<T, ElevenLabsSTTError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, ElevenLabsSTTError>>,
+    {
+        self.run_with(operation, true).await
+    }
+
+    /// Like `run`, for billed uploads: errors after which the API may already be processing
+    /// the request are only retried when `retry_uncertain_uploads` is enabled
+    pub(crate) async fn run_upload<T, F, Fut>(&self, operation: F) -> Result<T, ElevenLabsSTTError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, ElevenLabsSTTError>>,
+    {
+        self.run_with(operation, self.retry_uncertain_uploads).await
+    }
+
+    async fn run_with<T, F, Fut>(
+        &self,
+        mut operation: F,
+        retry_uncertain: bool,
+    ) -> Result<T, ElevenLabsSTTError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, ElevenLabsSTTError>>,
+    {
+        let mut attempt = 1;
+
+        loop {
+            match operation().await {
+                Ok(value) => return Ok(value),
+                Err(error) if !retry_uncertain && error.may_have_been_processed() => {
+                    return Err(error);
+                }
+                Err(error) => match self.next_delay(attempt, &error) {
+                    Some(delay) => tokio::time::sleep(delay).await,
+                    None => return Err(error),
+                },
+            }
+
+            attempt += 1;
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("multiplier", &self.multiplier)
+            .field("jitter", &self.jitter)
+            .field("respect_retry_after", &self.respect_retry_after)
+            .field("retry_uncertain_uploads", &self.retry_uncertain_uploads)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server_error() -> ElevenLabsSTTError {
+        ElevenLabsSTTError::ApiError {
+            status: 503,
+            message: "unavailable".to_string(),
+            retry_after: None,
+        }
+    }
+
+    #[test]
+    fn test_exponential_backoff_without_jitter() {
+        let policy = RetryPolicy::new()
+            .max_attempts(5)
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(350))
+            .jitter(false);
+
+        assert_eq!(
+            policy.next_delay(1, &server_error()),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            policy.next_delay(2, &server_error()),
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(
+            policy.next_delay(3, &server_error()),
+            Some(Duration::from_millis(350))
+        );
+        assert_eq!(policy.next_delay(5, &server_error()), None);
+    }
+
+    #[test]
+    fn test_non_retryable_and_retry_after() {
+        let policy = RetryPolicy::new().jitter(false);
+
+        let validation = ElevenLabsSTTError::ValidationError("bad".to_string());
+        assert_eq!(policy.next_delay(1, &validation), None);
+
+        let rate_limited = ElevenLabsSTTError::RateLimitError {
+            retry_after: Some(7),
+            message: "slow down".to_string(),
+        };
+        assert_eq!(
+            policy.next_delay(1, &rate_limited),
+            Some(Duration::from_secs(7))
+        );
+
+        let rate_limited_for_a_day = ElevenLabsSTTError::RateLimitError {
+            retry_after: Some(86_400),
+            message: "come back tomorrow".to_string(),
+        };
+        assert_eq!(policy.next_delay(1, &rate_limited_for_a_day), None);
+
+        let unavailable = ElevenLabsSTTError::ApiError {
+            status: 503,
+            message: "maintenance".to_string(),
+            retry_after: Some(3),
+        };
+        assert_eq!(
+            policy.next_delay(1, &unavailable),
+            Some(Duration::from_secs(3))
+        );
+
+        let custom = RetryPolicy::new().retry_if(|_| false);
+        assert_eq!(custom.next_delay(1, &server_error()), None);
+    }
+
+    #[tokio::test]
+    async fn test_run_retries_until_success() {
+        let policy = RetryPolicy::new()
+            .max_attempts(3)
+            .initial_backoff(Duration::from_millis(1));
+        let mut calls = 0;
+
+        let result = policy
+            .run(|| {
+                calls += 1;
+                let current = calls;
+                async move {
+                    if current < 3 {
+                        Err(server_error())
+                    } else {
+                        Ok(current)
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 3);
+    }
+}
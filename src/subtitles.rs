@@ -0,0 +1,397 @@
+use crate::types::{STTResponse, STTResponseWord};
+use std::borrow::Cow;
+
+/// How words are grouped into subtitle cues
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtitleOptions {
+    /// Maximum characters on a single line
+    pub max_chars_per_line: usize,
+    /// Maximum lines in a single cue
+    pub max_lines: usize,
+    /// Maximum cue duration in seconds
+    pub max_cue_duration: f32,
+    /// End the cue after a word ending a sentence (`.`, `!`, `?`, `…`)
+    pub break_on_punctuation: bool,
+    /// Start a new cue when the diarized speaker changes
+    pub break_on_speaker_change: bool,
+}
+
+impl Default for SubtitleOptions {
+    fn default() -> Self {
+        Self {
+            max_chars_per_line: 42,
+            max_lines: 2,
+            max_cue_duration: 7.0,
+            break_on_punctuation: true,
+            break_on_speaker_change: true,
+        }
+    }
+}
+
+/// WebVTT rendering options
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WebVttOptions {
+    /// How words are grouped into cues
+    pub cues: SubtitleOptions,
+    /// Wrap cue text in `<v speaker_id>` voice tags when words carry a `speaker_id`
+    pub speaker_tags: bool,
+    /// Emit a `<00:00:01.000>` timestamp before each word for karaoke-style highlighting
+    pub word_timestamps: bool,
+}
+
+/// A timed word placed in a cue
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CueWord {
+    pub(crate) text: String,
+    pub(crate) start: f32,
+    pub(crate) end: f32,
+    pub(crate) speaker_id: Option<String>,
+}
+
+/// A group of words displayed together
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Cue {
+    pub(crate) start: f32,
+    pub(crate) end: f32,
+    pub(crate) words: Vec<CueWord>,
+}
+
+impl Cue {
+    /// Cue words wrapped into lines of at most `max_chars` characters
+    pub(crate) fn lines(&self, max_chars: usize) -> Vec<&[CueWord]> {
+        let mut rest = self.words.as_slice();
+
+        wrap(self.words.iter().map(|word| word.text.as_str()), max_chars)
+            .into_iter()
+            .map(|count| {
+                let (line, tail) = rest.split_at(count);
+                rest = tail;
+                line
+            })
+            .collect()
+    }
+
+    /// Plain cue text, one wrapped line per row
+    pub(crate) fn text(&self, max_chars: usize) -> String {
+        self.lines(max_chars)
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|word| word.text.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Speaker of the first word in the cue
+    pub(crate) fn speaker_id(&self) -> Option<&str> {
+        self.words
+            .first()
+            .and_then(|word| word.speaker_id.as_deref())
+    }
+}
+
+/// Greedily wrap words into lines, returning the number of words on each line.
+/// A word longer than a line gets a line of its own.
+fn wrap<'a>(words: impl Iterator<Item = &'a str>, max_chars: usize) -> Vec<usize> {
+    // (words, characters) per line
+    let mut lines: Vec<(usize, usize)> = Vec::new();
+
+    for word in words {
+        let len = word.chars().count();
+        match lines.last_mut() {
+            Some((count, chars)) if *chars + 1 + len <= max_chars => {
+                *count += 1;
+                *chars += 1 + len;
+            }
+            _ => lines.push((1, len)),
+        }
+    }
+
+    lines.into_iter().map(|(count, _)| count).collect()
+}
+
+fn ends_sentence(text: &str) -> bool {
+    text.trim_end_matches(['"', '\'', ')', ']'])
+        .ends_with(['.', '!', '?', '…'])
+}
+
+/// Timed, non-spacing words of the response
+fn timed_words(words: &[STTResponseWord]) -> Vec<CueWord> {
+    words
+        .iter()
+        .filter(|word| word.type_field.as_deref() != Some("spacing"))
+        .filter_map(|word| {
+            let text = word.text.as_deref()?.trim();
+            if text.is_empty() {
+                return None;
+            }
+
+            let start = word.start?;
+            Some(CueWord {
+                text: text.to_string(),
+                start,
+                end: word.end.unwrap_or(start).max(start),
+                speaker_id: word.speaker_id.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Words of the response, with multichannel transcripts merged in time order
+fn subtitle_words(response: &STTResponse) -> Cow<'_, [STTResponseWord]> {
+    if response.is_multichannel() {
+        Cow::Owned(response.merge_channels().words.unwrap_or_default())
+    } else {
+        Cow::Borrowed(response.words.as_deref().unwrap_or_default())
+    }
+}
+
+/// Group words into cues according to `options`
+pub(crate) fn build_cues(words: &[STTResponseWord], options: &SubtitleOptions) -> Vec<Cue> {
+    let max_chars = options.max_chars_per_line.max(1);
+    let max_lines = options.max_lines.max(1);
+    let mut cues: Vec<Cue> = Vec::new();
+    let mut current: Option<Cue> = None;
+
+    for word in timed_words(words) {
+        if let Some(cue) = current.take() {
+            let candidate = cue
+                .words
+                .iter()
+                .map(|w| w.text.as_str())
+                .chain(std::iter::once(word.text.as_str()));
+            let fits_lines = wrap(candidate, max_chars).len() <= max_lines;
+            let fits_duration = word.end - cue.start <= options.max_cue_duration;
+            let same_speaker =
+                !options.break_on_speaker_change || cue.speaker_id() == word.speaker_id.as_deref();
+
+            if fits_lines && fits_duration && same_speaker {
+                current = Some(cue);
+            } else {
+                cues.push(cue);
+            }
+        }
+
+        let sentence_end = options.break_on_punctuation && ends_sentence(&word.text);
+        let cue = current.get_or_insert_with(|| Cue {
+            start: word.start,
+            end: word.end,
+            words: Vec::new(),
+        });
+        cue.end = cue.end.max(word.end);
+        cue.words.push(word);
+
+        if sentence_end {
+            cues.extend(current.take());
+        }
+    }
+
+    cues.extend(current);
+    cues
+}
+
+/// Format seconds as `HH:MM:SS<separator>mmm`
+pub(crate) fn format_timestamp(seconds: f32, separator: char) -> String {
+    let total_millis = (seconds.max(0.0) as f64 * 1000.0).round() as u64;
+    let hours = total_millis / 3_600_000;
+    let minutes = total_millis / 60_000 % 60;
+    let secs = total_millis / 1000 % 60;
+    let millis = total_millis % 1000;
+
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        hours, minutes, secs, separator, millis
+    )
+}
+
+impl STTResponse {
+    /// Render the transcription as SubRip (SRT) subtitles.
+    /// Returns an empty string if the response has no timed words.
+    pub fn to_srt(&self, options: &SubtitleOptions) -> String {
+        let words = subtitle_words(self);
+        let words = words.as_ref();
+        let mut srt = String::new();
+
+        for (index, cue) in build_cues(words, options).iter().enumerate() {
+            srt.push_str(&format!(
+                "{}\n{} --> {}\n{}\n\n",
+                index + 1,
+                format_timestamp(cue.start, ','),
+                format_timestamp(cue.end, ','),
+                cue.text(options.max_chars_per_line.max(1))
+            ));
+        }
+
+        srt
+    }
+
+    /// Render the transcription as WebVTT subtitles.
+    /// Returns just the `WEBVTT` header if the response has no timed words.
+    pub fn to_webvtt(&self, options: &WebVttOptions) -> String {
+        let words = subtitle_words(self);
+        let words = words.as_ref();
+        let max_chars = options.cues.max_chars_per_line.max(1);
+        let mut cue_options = options.cues.clone();
+        if options.speaker_tags {
+            cue_options.break_on_speaker_change = true;
+        }
+
+        let mut vtt = String::from("WEBVTT\n\n");
+
+        for cue in build_cues(words, &cue_options) {
+            let mut text = String::new();
+
+            if options.speaker_tags
+                && let Some(speaker_id) = cue.speaker_id()
+            {
+                text.push_str(&format!("<v {}>", escape_vtt(speaker_id)));
+            }
+
+            for (line_index, line) in cue.lines(max_chars).iter().enumerate() {
+                if line_index > 0 {
+                    text.push('\n');
+                }
+
+                for (word_index, word) in line.iter().enumerate() {
+                    if word_index > 0 {
+                        text.push(' ');
+                    }
+                    if options.word_timestamps && word.start > cue.start {
+                        text.push_str(&format!("<{}>", format_timestamp(word.start, '.')));
+                    }
+                    text.push_str(&escape_vtt(&word.text));
+                }
+            }
+
+            vtt.push_str(&format!(
+                "{} --> {}\n{}\n\n",
+                format_timestamp(cue.start, '.'),
+                format_timestamp(cue.end, '.'),
+                text
+            ));
+        }
+
+        vtt
+    }
+}
+
+/// Escape characters with special meaning in WebVTT cue text
+fn escape_vtt(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, start: f32, end: f32, speaker: &str) -> STTResponseWord {
+        STTResponseWord {
+            text: Some(text.to_string()),
+            start: Some(start),
+            end: Some(end),
+            type_field: Some("word".to_string()),
+            speaker_id: Some(speaker.to_string()),
+            ..STTResponseWord::default()
+        }
+    }
+
+    fn spacing(start: f32) -> STTResponseWord {
+        STTResponseWord {
+            text: Some(" ".to_string()),
+            start: Some(start),
+            end: Some(start),
+            type_field: Some("spacing".to_string()),
+            ..STTResponseWord::default()
+        }
+    }
+
+    fn response(words: Vec<STTResponseWord>) -> STTResponse {
+        STTResponse {
+            words: Some(words),
+            ..STTResponse::default()
+        }
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0.0, ','), "00:00:00,000");
+        assert_eq!(format_timestamp(3723.456, '.'), "01:02:03.456");
+    }
+
+    #[test]
+    fn test_to_srt_breaks_on_punctuation() {
+        let response = response(vec![
+            word("Hello", 0.0, 0.5, "speaker_0"),
+            spacing(0.5),
+            word("world.", 0.6, 1.0, "speaker_0"),
+            spacing(1.0),
+            word("Bye", 1.5, 2.0, "speaker_0"),
+        ]);
+
+        assert_eq!(
+            response.to_srt(&SubtitleOptions::default()),
+            "1\n00:00:00,000 --> 00:00:01,000\nHello world.\n\n\
+             2\n00:00:01,500 --> 00:00:02,000\nBye\n\n"
+        );
+    }
+
+    #[test]
+    fn test_to_srt_line_and_duration_limits() {
+        let response = response(vec![
+            word("one", 0.0, 1.0, "speaker_0"),
+            word("two", 1.0, 2.0, "speaker_0"),
+            word("three", 2.0, 3.0, "speaker_0"),
+            word("four", 3.0, 9.0, "speaker_0"),
+        ]);
+        let options = SubtitleOptions {
+            max_chars_per_line: 8,
+            max_lines: 2,
+            max_cue_duration: 5.0,
+            ..SubtitleOptions::default()
+        };
+
+        assert_eq!(
+            response.to_srt(&options),
+            "1\n00:00:00,000 --> 00:00:03,000\none two\nthree\n\n\
+             2\n00:00:03,000 --> 00:00:09,000\nfour\n\n"
+        );
+    }
+
+    #[test]
+    fn test_to_webvtt_with_speakers_and_word_timestamps() {
+        let response = response(vec![
+            word("Hi", 0.0, 0.4, "speaker_0"),
+            spacing(0.4),
+            word("there", 0.5, 1.0, "speaker_0"),
+            spacing(1.0),
+            word("<yes>", 1.2, 1.6, "speaker_1"),
+        ]);
+        let options = WebVttOptions {
+            speaker_tags: true,
+            word_timestamps: true,
+            ..WebVttOptions::default()
+        };
+
+        assert_eq!(
+            response.to_webvtt(&options),
+            "WEBVTT\n\n\
+             00:00:00.000 --> 00:00:01.000\n<v speaker_0>Hi <00:00:00.500>there\n\n\
+             00:00:01.200 --> 00:00:01.600\n<v speaker_1>&lt;yes&gt;\n\n"
+        );
+    }
+
+    #[test]
+    fn test_to_webvtt_plain() {
+        let response = response(vec![word("Hello.", 1.0, 2.0, "speaker_0")]);
+
+        assert_eq!(
+            response.to_webvtt(&WebVttOptions::default()),
+            "WEBVTT\n\n00:00:01.000 --> 00:00:02.000\nHello.\n\n"
+        );
+    }
+}
@@ -1,4 +1,8 @@
+use crate::formats::{AdditionalFormat, ExportedFormat};
+use crate::language::LanguageCode;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct STTRequest {
@@ -9,15 +13,15 @@ pub struct STTRequest {
     pub file: Option<Vec<u8>>,
 
     // The ID of the model to use for transcription.
-    // Currently only `scribe_v1` and `scribe_v1_experimental` are available.
+    // Known models are `scribe_v1`, `scribe_v1_experimental` and `scribe_v2` (see `Model`); keyterms need `scribe_v2`.
     pub model_id: String,
 
-    // Language code (ISO 639-1) used to enforce a language for the model. Currently only Turbo v2.5 and Flash v2.5 support language enforcement.
+    // Language code (ISO 639-1 or ISO 639-3, normalized to ISO 639-3) used to enforce a language for the model. Currently only Turbo v2.5 and Flash v2.5 support language enforcement.
     // For other models, an error will be returned if language code is provided.
     // You can see all supported languages for each model: https://help.elevenlabs.io/hc/en-us/articles/13313366263441-What-languages-do-you-support
     // Note: this parameter in ElevenLabs API doesn't translate text - it only controls the pronunciation/accent when speaking the text.
     // The text itself remains in the original language. i.e: If you want French audio, you need to provide French text.
-    pub language_code: Option<String>,
+    pub language_code: Option<LanguageCode>,
 
     // Whether to tag audio events like (laughter), (footsteps), etc. in the transcription.
     // Defaults to true
@@ -30,7 +34,7 @@ pub struct STTRequest {
 
     // The granularity of the timestamps in the transcription. ‘word’ provides word-level timestamps and ‘character’ provides character-level timestamps per word.
     // Allowed values: none, word, character. Defaults to word.
-    pub timestamps_granularity: Option<String>,
+    pub timestamps_granularity: Option<TimestampsGranularity>,
 
     // Whether to annotate which speaker is currently talking in the uploaded file.
     // Defaults to false
@@ -81,22 +85,288 @@ pub struct STTRequest {
     // Useful for tracking internal IDs, job references, or other contextual information.
     // Defaults to None
     pub webhook_metadata: Option<String>,
+
+    // The format of the input audio. `pcm_s16le_16` is raw 16-bit PCM at 16kHz, mono, little-endian,
+    // and has lower latency than encoded audio. `other` accepts any encoded audio or video container.
+    // Defaults to other
+    pub file_format: Option<FileFormat>,
+
+    // Words or phrases to bias the transcription towards, such as product names and jargon.
+    // At most 100 keyterms, each shorter than 50 characters and at most 5 words.
+    // Only supported by models with keyterm prompting (scribe_v2).
+    // Defaults to None
+    pub keyterms: Option<Vec<String>>,
+
+    // Extra formats to render server-side and return with the transcript: srt, txt, segmented_json, docx, pdf, html.
+    // Each format can be requested once, with its own segmentation options.
+    // Defaults to None
+    pub additional_formats: Option<Vec<AdditionalFormat>>,
+}
+
+/// Granularity of the timestamps returned in the transcription
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampsGranularity {
+    /// No timestamps
+    None,
+    /// Word-level timestamps (API default)
+    Word,
+    /// Character-level timestamps per word
+    Character,
+}
+
+impl TimestampsGranularity {
+    /// Value sent to the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimestampsGranularity::None => "none",
+            TimestampsGranularity::Word => "word",
+            TimestampsGranularity::Character => "character",
+        }
+    }
+}
+
+impl fmt::Display for TimestampsGranularity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TimestampsGranularity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "none" => Ok(TimestampsGranularity::None),
+            "word" => Ok(TimestampsGranularity::Word),
+            "character" => Ok(TimestampsGranularity::Character),
+            other => Err(format!(
+                "Invalid timestamps_granularity '{}': expected none, word or character",
+                other
+            )),
+        }
+    }
+}
+
+/// Format of the uploaded audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum FileFormat {
+    /// Raw 16-bit little-endian PCM, 16kHz mono, without any container
+    #[serde(rename = "pcm_s16le_16")]
+    PcmS16le16,
+    /// Any encoded audio or video file (API default)
+    #[default]
+    #[serde(rename = "other")]
+    Other,
+}
+
+impl FileFormat {
+    /// Value sent to the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FileFormat::PcmS16le16 => "pcm_s16le_16",
+            FileFormat::Other => "other",
+        }
+    }
 }
 
-/// Voice settings for fine-tuning speech output
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl fmt::Display for FileFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for FileFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "pcm_s16le_16" => Ok(FileFormat::PcmS16le16),
+            "other" => Ok(FileFormat::Other),
+            other => Err(format!(
+                "Invalid file_format '{}': expected pcm_s16le_16 or other",
+                other
+            )),
+        }
+    }
+}
+
+/// Transcription result.
+/// With `use_multi_channel` enabled, per-channel results are in `transcripts`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct STTResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub language_code: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "crate::language::deserialize_optional",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub language_code: Option<LanguageCode>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub language_probability: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub words: Option<Vec<STTResponseWord>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_index: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcripts: Option<Vec<STTResponse>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcription_id: Option<String>,
+    /// Documents requested with `additional_formats`, decoded from base64 where needed.
+    /// Malformed entries are skipped rather than failing the whole response.
+    #[serde(
+        default,
+        deserialize_with = "crate::formats::deserialize_exported_formats",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub additional_formats: Option<Vec<ExportedFormat>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl STTResponse {
+    /// The rendered document for a requested format name (`"srt"`, `"pdf"`, ...)
+    pub fn additional_format(&self, format: &str) -> Option<&ExportedFormat> {
+        self.additional_formats
+            .iter()
+            .flatten()
+            .find(|exported| exported.requested_format == format)
+    }
+
+    /// Whether this is a multichannel response holding one transcript per channel
+    pub fn is_multichannel(&self) -> bool {
+        self.transcripts.is_some()
+    }
+
+    /// Per-channel transcripts, or the response itself for single-channel audio
+    pub fn channels(&self) -> Vec<&STTResponse> {
+        match &self.transcripts {
+            Some(transcripts) => transcripts.iter().collect(),
+            None => vec![self],
+        }
+    }
+
+    /// Merge all channels into a single transcript ordered by word start time.
+    /// Spacing entries are regenerated between words; each word keeps its `channel_index`.
+    /// Single-channel responses are returned unchanged.
+    pub fn merge_channels(&self) -> STTResponse {
+        let Some(transcripts) = &self.transcripts else {
+            return self.clone();
+        };
+
+        let mut words: Vec<STTResponseWord> = transcripts
+            .iter()
+            .flat_map(|transcript| {
+                transcript
+                    .words
+                    .iter()
+                    .flatten()
+                    .map(|word| STTResponseWord {
+                        channel_index: word.channel_index.or(transcript.channel_index),
+                        ..word.clone()
+                    })
+            })
+            .filter(|word| word.type_field.as_deref() != Some("spacing"))
+            .collect();
+
+        // Stable sort keeps channel order for words starting at the same time
+        words.sort_by(|a, b| {
+            a.start
+                .unwrap_or(f32::MAX)
+                .total_cmp(&b.start.unwrap_or(f32::MAX))
+        });
+
+        let mut merged_words = Vec::with_capacity(words.len() * 2);
+        for word in words {
+            if let Some(previous_end) = merged_words
+                .last()
+                .map(|previous: &STTResponseWord| previous.end)
+            {
+                // Channels often overlap, so the next word may start before the previous ends
+                let end = match (previous_end, word.start) {
+                    (Some(previous_end), Some(start)) => Some(start.max(previous_end)),
+                    (_, start) => start,
+                };
+                merged_words.push(STTResponseWord {
+                    text: Some(" ".to_string()),
+                    start: previous_end,
+                    end,
+                    type_field: Some("spacing".to_string()),
+                    ..STTResponseWord::default()
+                });
+            }
+            merged_words.push(word);
+        }
+
+        let text = merged_words
+            .iter()
+            .filter_map(|word| word.text.as_deref())
+            .collect::<String>();
+        let first = transcripts.first();
+
+        STTResponse {
+            text: Some(text),
+            language_code: first.and_then(|t| t.language_code.clone()),
+            language_probability: first.and_then(|t| t.language_probability),
+            words: Some(merged_words),
+            channel_index: None,
+            transcripts: None,
+            transcription_id: self.transcription_id.clone(),
+            additional_formats: self.additional_formats.clone(),
+        }
+    }
+}
+
+/// Returned instead of a transcript when the request is sent with `webhook=true`.
+/// The transcription is delivered later to the configured webhook.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WebhookAcknowledgment {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcription_id: Option<String>,
+}
+
+/// Subscription of the account owning the API key, with its credit usage
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Subscription {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tier: Option<String>,
+    /// Credits used in the current billing period
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub character_count: Option<u64>,
+    /// Credits available in the current billing period
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub character_limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_extend_character_limit: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_to_extend_character_limit: Option<bool>,
+    /// Unix timestamp at which `character_count` resets
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_character_count_reset_unix: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub billing_period: Option<String>,
+}
+
+impl Subscription {
+    /// Credits left in the current billing period
+    pub fn remaining_credits(&self) -> Option<u64> {
+        Some(self.character_limit?.saturating_sub(self.character_count?))
+    }
+
+    /// When the used credits reset
+    pub fn next_reset(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::DateTime::from_timestamp(self.next_character_count_reset_unix?, 0)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct STTResponseWord {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
@@ -112,9 +382,11 @@ pub struct STTResponseWord {
     pub speaker_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub characters: Option<Vec<STTResponseWordCharacters>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_index: Option<u32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct STTResponseWordCharacters {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
@@ -123,3 +395,123 @@ pub struct STTResponseWordCharacters {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub end: Option<f32>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multichannel_response() {
+        let json = r#"{
+            "transcripts": [
+                {"channel_index": 0, "language_code": "eng", "text": "Hi there",
+                 "words": [
+                    {"text": "Hi", "start": 0.0, "end": 0.3, "type": "word", "channel_index": 0},
+                    {"text": " ", "start": 0.3, "end": 1.0, "type": "spacing", "channel_index": 0},
+                    {"text": "there", "start": 1.0, "end": 1.4, "type": "word", "channel_index": 0}
+                 ]},
+                {"channel_index": 1, "language_code": "eng", "text": "Hello",
+                 "words": [
+                    {"text": "Hello", "start": 0.5, "end": 0.9, "type": "word", "channel_index": 1}
+                 ]}
+            ],
+            "transcription_id": "abc"
+        }"#;
+
+        let response: STTResponse = serde_json::from_str(json).unwrap();
+        assert!(response.is_multichannel());
+        assert_eq!(response.channels().len(), 2);
+
+        let merged = response.merge_channels();
+        assert_eq!(merged.text.as_deref(), Some("Hi Hello there"));
+        let channels: Vec<_> = merged
+            .words
+            .unwrap()
+            .iter()
+            .filter(|w| w.type_field.as_deref() == Some("word"))
+            .map(|w| w.channel_index)
+            .collect();
+        assert_eq!(channels, vec![Some(0), Some(1), Some(0)]);
+    }
+
+    #[test]
+    fn test_merge_overlapping_channels() {
+        let json = r#"{
+            "transcripts": [
+                {"channel_index": 0, "words": [
+                    {"text": "Hello", "start": 0.0, "end": 1.0, "type": "word"}
+                ]},
+                {"channel_index": 1, "words": [
+                    {"text": "Hi", "start": 0.5, "end": 0.8, "type": "word"}
+                ]}
+            ]
+        }"#;
+
+        let response: STTResponse = serde_json::from_str(json).unwrap();
+        let merged = response.merge_channels();
+        assert_eq!(merged.text.as_deref(), Some("Hello Hi"));
+
+        let words = merged.words.unwrap();
+        assert_eq!(words[1].type_field.as_deref(), Some("spacing"));
+        assert!(words.iter().all(|w| w.end.unwrap() >= w.start.unwrap()));
+    }
+
+    #[test]
+    fn test_subscription() {
+        let json = r#"{
+            "tier": "creator",
+            "character_count": 17231,
+            "character_limit": 100000,
+            "next_character_count_reset_unix": 1738356858,
+            "status": "active",
+            "voice_slots_used": 3
+        }"#;
+        let subscription: Subscription = serde_json::from_str(json).unwrap();
+
+        assert_eq!(subscription.tier.as_deref(), Some("creator"));
+        assert_eq!(subscription.remaining_credits(), Some(82769));
+        assert_eq!(
+            subscription.next_reset().unwrap().to_rfc3339(),
+            "2025-01-31T20:54:18+00:00"
+        );
+        assert_eq!(Subscription::default().remaining_credits(), None);
+    }
+
+    #[test]
+    fn test_bad_additional_format_keeps_transcript() {
+        let json = r#"{"text": "hello", "additional_formats": [
+            {"requested_format": "pdf", "file_extension": "pdf", "content_type": "application/pdf",
+             "is_base64_encoded": true, "content": "%%%"},
+            null,
+            {"requested_format": "txt"},
+            {"requested_format": "txt", "file_extension": "txt", "content_type": "text/plain",
+             "is_base64_encoded": false, "content": "hello"}
+        ]}"#;
+        let response: STTResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.text.as_deref(), Some("hello"));
+        let formats = response.additional_formats.as_ref().unwrap();
+        assert_eq!(formats.len(), 2);
+        assert!(
+            response
+                .additional_format("pdf")
+                .unwrap()
+                .decode_error
+                .is_some()
+        );
+        assert_eq!(
+            response.additional_format("txt").unwrap().text(),
+            Some("hello")
+        );
+    }
+
+    #[test]
+    fn test_webhook_acknowledgment() {
+        let json =
+            r#"{"message": "Request accepted", "request_id": "req_1", "transcription_id": "tr_1"}"#;
+        let ack: WebhookAcknowledgment = serde_json::from_str(json).unwrap();
+
+        assert_eq!(ack.request_id.as_deref(), Some("req_1"));
+        assert_eq!(ack.transcription_id.as_deref(), Some("tr_1"));
+    }
+}
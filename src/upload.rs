@@ -0,0 +1,269 @@
+use crate::audio::{AudioFormat, SNIFF_LEN};
+use crate::error::ElevenLabsSTTError;
+use bytes::Bytes;
+use reqwest::multipart::Part;
+use std::borrow::Cow;
+use std::io::{Cursor, Read};
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio_util::io::ReaderStream;
+use tokio_util::sync::CancellationToken;
+
+/// Boxed reader accepted by [`crate::ElevenLabsSTTClient::speech_to_text_reader`]
+pub(crate) type BoxedReader = Pin<Box<dyn AsyncRead + Send>>;
+
+/// Progress of the file upload for the current attempt.
+/// Counts file bytes handed to the HTTP body; multipart framing is not included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct UploadProgress {
+    pub bytes_sent: u64,
+    pub total_bytes: u64,
+}
+
+/// Callback invoked as upload bytes are sent
+pub type ProgressCallback = Arc<dyn Fn(UploadProgress) + Send + Sync>;
+
+/// Progress reporting, cancellation and file type settings for one request
+#[derive(Clone, Default)]
+pub(crate) struct UploadControl {
+    pub(crate) progress: Option<ProgressCallback>,
+    pub(crate) cancellation: Option<CancellationToken>,
+    /// The file is headerless PCM, so it is never sniffed for a media type
+    pub(crate) raw_pcm: bool,
+}
+
+/// Where the multipart `file` part is read from
+pub(crate) enum UploadSource {
+    /// In-memory bytes, shared cheaply between retry attempts
+    Bytes(Bytes),
+    /// A file on disk, re-opened and streamed for every attempt
+    Path(PathBuf),
+    /// An arbitrary reader with a known length, can only be sent once
+    Reader {
+        reader: Option<BoxedReader>,
+        length: u64,
+    },
+}
+
+impl UploadSource {
+    /// Whether the source can be sent again after a failed attempt
+    pub(crate) fn is_replayable(&self) -> bool {
+        !matches!(self, UploadSource::Reader { .. })
+    }
+
+    /// Size of the upload in bytes
+    pub(crate) fn len(&self) -> Result<u64, ElevenLabsSTTError> {
+        match self {
+            UploadSource::Bytes(bytes) => Ok(bytes.len() as u64),
+            UploadSource::Path(path) => Ok(std::fs::metadata(path)
+                .map_err(ElevenLabsSTTError::IoError)?
+                .len()),
+            UploadSource::Reader { length, .. } => Ok(*length),
+        }
+    }
+
+    /// Leading bytes used to sniff the container, `None` for readers which cannot be peeked
+    pub(crate) fn header(&self) -> Result<Option<Cow<'_, [u8]>>, ElevenLabsSTTError> {
+        match self {
+            UploadSource::Bytes(bytes) => {
+                Ok(Some(Cow::Borrowed(&bytes[..bytes.len().min(SNIFF_LEN)])))
+            }
+            UploadSource::Path(path) => {
+                let file = std::fs::File::open(path).map_err(ElevenLabsSTTError::IoError)?;
+                let mut header = Vec::with_capacity(SNIFF_LEN);
+                file.take(SNIFF_LEN as u64)
+                    .read_to_end(&mut header)
+                    .map_err(ElevenLabsSTTError::IoError)?;
+                Ok(Some(Cow::Owned(header)))
+            }
+            UploadSource::Reader { .. } => Ok(None),
+        }
+    }
+
+    /// File name sent with the multipart part, keeping the name of files read from disk
+    pub(crate) fn file_name(&self, format: Option<AudioFormat>) -> String {
+        if let UploadSource::Path(path) = self
+            && let Some(name) = path.file_name()
+        {
+            return name.to_string_lossy().into_owned();
+        }
+
+        match format {
+            Some(format) => format!("file.{}", format.extension()),
+            None => "file".to_string(),
+        }
+    }
+
+    /// Build the multipart part for one attempt, reporting progress if requested
+    pub(crate) fn next_part(
+        &mut self,
+        control: &UploadControl,
+    ) -> Result<Part, ElevenLabsSTTError> {
+        // Raw samples can look like any container by chance, so PCM is sent untyped
+        let format = if control.raw_pcm {
+            None
+        } else {
+            self.header()?
+                .and_then(|header| AudioFormat::sniff(&header))
+        };
+        let file_name = self.file_name(format);
+        let mime_type = format.map_or("application/octet-stream", |f| f.mime_type());
+
+        let part = match (self, control.progress.as_ref()) {
+            (UploadSource::Bytes(bytes), None) => Part::stream(bytes.clone()),
+            (UploadSource::Bytes(bytes), Some(progress)) => {
+                let length = bytes.len() as u64;
+                streamed_part(Cursor::new(bytes.clone()), length, Some(progress))
+            }
+            (UploadSource::Path(path), progress) => {
+                let file = std::fs::File::open(&*path).map_err(ElevenLabsSTTError::IoError)?;
+                let length = file.metadata().map_err(ElevenLabsSTTError::IoError)?.len();
+                streamed_part(tokio::fs::File::from_std(file), length, progress)
+            }
+            (UploadSource::Reader { reader, length }, progress) => {
+                let reader = reader.take().ok_or_else(|| {
+                    ElevenLabsSTTError::ValidationError(
+                        "file reader has already been consumed".to_string(),
+                    )
+                })?;
+                streamed_part(reader, *length, progress)
+            }
+        };
+
+        part.file_name(file_name)
+            .mime_str(mime_type)
+            .map_err(ElevenLabsSTTError::RequestError)
+    }
+}
+
+/// Stream `reader` as a part of known length, counting bytes as they are read
+fn streamed_part<R>(reader: R, length: u64, progress: Option<&ProgressCallback>) -> Part
+where
+    R: AsyncRead + Send + 'static,
+{
+    let reader = ProgressReader {
+        inner: Box::pin(reader),
+        progress: progress.cloned(),
+        state: UploadProgress {
+            bytes_sent: 0,
+            total_bytes: length,
+        },
+    };
+
+    Part::stream_with_length(
+        reqwest::Body::wrap_stream(ReaderStream::new(reader)),
+        length,
+    )
+}
+
+/// Reader adapter reporting how many bytes have been read so far
+struct ProgressReader {
+    inner: BoxedReader,
+    progress: Option<ProgressCallback>,
+    state: UploadProgress,
+}
+
+impl AsyncRead for ProgressReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let before = buf.filled().len();
+        let poll = self.inner.as_mut().poll_read(cx, buf);
+
+        if let Poll::Ready(Ok(())) = &poll {
+            let read = (buf.filled().len() - before) as u64;
+            if read > 0 {
+                self.state.bytes_sent += read;
+                if let Some(progress) = &self.progress {
+                    progress(self.state);
+                }
+            }
+        }
+
+        poll
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tokio::io::AsyncReadExt;
+
+    #[test]
+    fn test_path_source() {
+        let mut source = UploadSource::Path(PathBuf::from("inputs/speech.mp3"));
+
+        assert!(source.is_replayable());
+        assert_eq!(source.file_name(None), "speech.mp3");
+        assert_eq!(
+            source.len().unwrap(),
+            std::fs::metadata("inputs/speech.mp3").unwrap().len()
+        );
+        assert!(source.next_part(&UploadControl::default()).is_ok());
+        assert!(source.next_part(&UploadControl::default()).is_ok());
+    }
+
+    #[test]
+    fn test_bytes_source_is_sniffed() {
+        let speech = std::fs::read("inputs/speech.mp3").unwrap();
+        let source = UploadSource::Bytes(Bytes::from(speech));
+
+        let format = AudioFormat::sniff(&source.header().unwrap().unwrap());
+        assert_eq!(format, Some(AudioFormat::Mp3));
+        assert_eq!(source.file_name(format), "file.mp3");
+        assert_eq!(UploadSource::Bytes(Bytes::new()).file_name(None), "file");
+    }
+
+    #[test]
+    fn test_reader_source_is_single_use() {
+        let mut source = UploadSource::Reader {
+            reader: Some(Box::pin(&b"audio"[..])),
+            length: 5,
+        };
+
+        assert!(!source.is_replayable());
+        assert!(source.next_part(&UploadControl::default()).is_ok());
+        assert!(matches!(
+            source.next_part(&UploadControl::default()),
+            Err(ElevenLabsSTTError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_missing_path() {
+        let mut source = UploadSource::Path(PathBuf::from("inputs/missing.mp3"));
+        assert!(matches!(
+            source.next_part(&UploadControl::default()),
+            Err(ElevenLabsSTTError::IoError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_progress_reader_reports_bytes() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let callback: ProgressCallback = Arc::new(move |p| sink.lock().unwrap().push(p));
+
+        let mut reader = ProgressReader {
+            inner: Box::pin(&b"0123456789"[..]),
+            progress: Some(callback),
+            state: UploadProgress {
+                bytes_sent: 0,
+                total_bytes: 10,
+            },
+        };
+
+        let mut buf = [0u8; 4];
+        while reader.read(&mut buf).await.unwrap() > 0 {}
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.last().unwrap().bytes_sent, 10);
+        assert!(seen.iter().all(|p| p.total_bytes == 10));
+    }
+}
@@ -0,0 +1,429 @@
+use crate::error::ElevenLabsSTTError;
+use crate::models::Model;
+use crate::types::{FileFormat, STTRequest};
+
+/// Maximum number of speakers the API can predict
+pub(crate) const MAX_NUM_SPEAKERS: u32 = 32;
+
+/// Largest accepted `seed`
+pub(crate) const MAX_SEED: u32 = 2_147_483_647;
+
+/// Accepted `temperature` range
+pub(crate) const TEMPERATURE_RANGE: std::ops::RangeInclusive<f32> = 0.0..=2.0;
+
+/// Maximum number of `keyterms` per request
+pub(crate) const MAX_KEYTERMS: usize = 100;
+
+/// Each keyterm must be shorter than this many characters
+pub(crate) const MAX_KEYTERM_LENGTH: usize = 50;
+
+/// Maximum number of words in a single keyterm
+pub(crate) const MAX_KEYTERM_WORDS: usize = 5;
+
+/// Maximum nesting depth of `webhook_metadata`
+pub(crate) const MAX_WEBHOOK_METADATA_DEPTH: usize = 2;
+
+/// Maximum serialized size of `webhook_metadata` in bytes
+pub(crate) const MAX_WEBHOOK_METADATA_SIZE: usize = 16 * 1024;
+
+fn invalid<T>(message: String) -> Result<T, ElevenLabsSTTError> {
+    Err(ElevenLabsSTTError::ValidationError(message))
+}
+
+/// Check every documented `STTRequest` constraint before sending.
+/// `file_size` is the upload size in bytes, or `None` when no file is attached.
+pub(crate) fn validate_request(
+    request: &STTRequest,
+    model: &Model,
+    file_size: Option<u64>,
+) -> Result<(), ElevenLabsSTTError> {
+    let capabilities = model.capabilities();
+
+    match (file_size, &request.cloud_storage_url) {
+        (Some(_), Some(_)) => {
+            return invalid(
+                "file, cloud_storage_url: exactly one must be provided, got both".to_string(),
+            );
+        }
+        (None, None) => {
+            return invalid(
+                "file, cloud_storage_url: exactly one must be provided, got neither".to_string(),
+            );
+        }
+        _ => {}
+    }
+
+    if let Some(size) = file_size
+        && size >= capabilities.max_file_size
+    {
+        return invalid(format!(
+            "file: {} bytes exceeds the {} byte limit of model '{}'",
+            size, capabilities.max_file_size, model
+        ));
+    }
+
+    if request.file_format == Some(FileFormat::PcmS16le16)
+        && let Some(size) = file_size
+        && size % 2 != 0
+    {
+        return invalid(format!(
+            "file: {} bytes is not a whole number of 16-bit samples for file_format={}",
+            size,
+            FileFormat::PcmS16le16
+        ));
+    }
+
+    if let Some(url) = &request.cloud_storage_url {
+        match reqwest::Url::parse(url) {
+            Ok(parsed) if parsed.scheme() == "https" => {}
+            _ => {
+                return invalid(format!(
+                    "cloud_storage_url: '{}' must be a valid HTTPS URL",
+                    url
+                ));
+            }
+        }
+    }
+
+    if request.language_code.is_some() && !capabilities.supports_language_enforcement {
+        return invalid(format!(
+            "language_code: model '{}' does not support language enforcement",
+            model
+        ));
+    }
+
+    if request.use_multi_channel == Some(true) && !capabilities.supports_multichannel {
+        return invalid(format!(
+            "use_multi_channel: model '{}' does not support multichannel audio",
+            model
+        ));
+    }
+
+    if let Some(keyterms) = &request.keyterms {
+        if !capabilities.supports_keyterms {
+            return invalid(format!(
+                "keyterms: model '{}' does not support keyterm prompting",
+                model
+            ));
+        }
+        validate_keyterms(keyterms)?;
+    }
+
+    if let Some(formats) = &request.additional_formats {
+        for (index, format) in formats.iter().enumerate() {
+            if formats[..index]
+                .iter()
+                .any(|previous| previous.as_str() == format.as_str())
+            {
+                return invalid(format!(
+                    "additional_formats: '{}' is requested more than once",
+                    format.as_str()
+                ));
+            }
+        }
+    }
+
+    if let Some(num_speakers) = request.num_speakers
+        && !(1..=MAX_NUM_SPEAKERS).contains(&num_speakers)
+    {
+        return invalid(format!(
+            "num_speakers: must be between 1 and {}, got {}",
+            MAX_NUM_SPEAKERS, num_speakers
+        ));
+    }
+
+    if request.diarization_threshold.is_some() {
+        if request.diarize != Some(true) {
+            return invalid("diarization_threshold: can only be set when diarize=true".to_string());
+        }
+        if request.num_speakers.is_some() {
+            return invalid(
+                "diarization_threshold: can only be set when num_speakers is None".to_string(),
+            );
+        }
+    }
+
+    if let Some(temperature) = request.temperature
+        && !TEMPERATURE_RANGE.contains(&temperature)
+    {
+        return invalid(format!(
+            "temperature: must be between {} and {}, got {}",
+            TEMPERATURE_RANGE.start(),
+            TEMPERATURE_RANGE.end(),
+            temperature
+        ));
+    }
+
+    if let Some(seed) = request.seed
+        && seed > MAX_SEED
+    {
+        return invalid(format!(
+            "seed: must be between 0 and {}, got {}",
+            MAX_SEED, seed
+        ));
+    }
+
+    if request.webhook_id.is_some() && request.webhook != Some(true) {
+        return invalid("webhook_id: can only be set when webhook=true".to_string());
+    }
+
+    if let Some(metadata) = &request.webhook_metadata {
+        validate_webhook_metadata(metadata).map_err(ElevenLabsSTTError::ValidationError)?;
+    }
+
+    Ok(())
+}
+
+/// Reject uploads that are clearly not audio or video, judging from their first bytes
+pub(crate) fn validate_media(header: &[u8]) -> Result<(), ElevenLabsSTTError> {
+    match crate::audio::non_media_kind(header) {
+        Some(kind) => invalid(format!(
+            "file: looks like {}, expected an audio or video file",
+            kind
+        )),
+        None => Ok(()),
+    }
+}
+
+/// At most 100 non-empty keyterms, each under 50 characters and 5 words
+fn validate_keyterms(keyterms: &[String]) -> Result<(), ElevenLabsSTTError> {
+    if keyterms.len() > MAX_KEYTERMS {
+        return invalid(format!(
+            "keyterms: at most {} keyterms are allowed, got {}",
+            MAX_KEYTERMS,
+            keyterms.len()
+        ));
+    }
+
+    for keyterm in keyterms {
+        let words = keyterm.split_whitespace().count();
+        if words == 0 {
+            return invalid("keyterms: keyterms must not be empty".to_string());
+        }
+        if keyterm.chars().count() >= MAX_KEYTERM_LENGTH {
+            return invalid(format!(
+                "keyterms: '{}' must be shorter than {} characters",
+                keyterm, MAX_KEYTERM_LENGTH
+            ));
+        }
+        if words > MAX_KEYTERM_WORDS {
+            return invalid(format!(
+                "keyterms: '{}' has {} words, at most {} are allowed",
+                keyterm, words, MAX_KEYTERM_WORDS
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// `webhook_metadata` must be a JSON object no deeper than 2 levels and at most 16KB
+pub(crate) fn validate_webhook_metadata(metadata: &str) -> Result<(), String> {
+    if metadata.len() > MAX_WEBHOOK_METADATA_SIZE {
+        return Err(format!(
+            "webhook_metadata: {} bytes exceeds the {} byte limit",
+            metadata.len(),
+            MAX_WEBHOOK_METADATA_SIZE
+        ));
+    }
+
+    let value: serde_json::Value = serde_json::from_str(metadata)
+        .map_err(|e| format!("webhook_metadata: invalid JSON: {}", e))?;
+
+    if !value.is_object() {
+        return Err("webhook_metadata: must be a JSON object".to_string());
+    }
+
+    let depth = json_depth(&value);
+    if depth > MAX_WEBHOOK_METADATA_DEPTH {
+        return Err(format!(
+            "webhook_metadata: nesting depth {} exceeds the maximum of {}",
+            depth, MAX_WEBHOOK_METADATA_DEPTH
+        ));
+    }
+
+    Ok(())
+}
+
+/// Nesting depth of objects and arrays, scalars count as 0
+fn json_depth(value: &serde_json::Value) -> usize {
+    match value {
+        serde_json::Value::Object(map) => 1 + map.values().map(json_depth).max().unwrap_or(0),
+        serde_json::Value::Array(items) => 1 + items.iter().map(json_depth).max().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::{AdditionalFormat, ExportOptions};
+    use crate::language::LanguageCode;
+
+    fn request() -> STTRequest {
+        STTRequest {
+            file: None,
+            model_id: "scribe_v1".to_string(),
+            language_code: None,
+            tag_audio_events: None,
+            num_speakers: None,
+            timestamps_granularity: None,
+            diarize: None,
+            diarization_threshold: None,
+            cloud_storage_url: None,
+            webhook: None,
+            webhook_id: None,
+            temperature: None,
+            seed: None,
+            use_multi_channel: None,
+            webhook_metadata: None,
+            file_format: None,
+            keyterms: None,
+            additional_formats: None,
+        }
+    }
+
+    fn error_message(request: &STTRequest, file_size: Option<u64>) -> String {
+        match validate_request(request, &Model::ScribeV1, file_size) {
+            Err(ElevenLabsSTTError::ValidationError(message)) => message,
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_file_or_cloud_storage_url() {
+        assert!(error_message(&request(), None).contains("got neither"));
+
+        let both = STTRequest {
+            cloud_storage_url: Some("https://example.com/a.mp3".to_string()),
+            ..request()
+        };
+        assert!(error_message(&both, Some(10)).contains("got both"));
+        assert!(validate_request(&both, &Model::ScribeV1, None).is_ok());
+
+        let http = STTRequest {
+            cloud_storage_url: Some("http://example.com/a.mp3".to_string()),
+            ..request()
+        };
+        assert!(error_message(&http, None).starts_with("cloud_storage_url"));
+
+        assert!(error_message(&request(), Some(crate::models::MAX_FILE_SIZE)).starts_with("file"));
+    }
+
+    #[test]
+    fn test_field_constraints() {
+        let speakers = STTRequest {
+            num_speakers: Some(33),
+            ..request()
+        };
+        assert!(error_message(&speakers, Some(1)).starts_with("num_speakers"));
+
+        let threshold = STTRequest {
+            diarization_threshold: Some(0.3),
+            ..request()
+        };
+        assert!(error_message(&threshold, Some(1)).contains("diarize=true"));
+
+        let threshold_with_speakers = STTRequest {
+            diarize: Some(true),
+            num_speakers: Some(2),
+            diarization_threshold: Some(0.3),
+            ..request()
+        };
+        assert!(error_message(&threshold_with_speakers, Some(1)).contains("num_speakers is None"));
+
+        let temperature = STTRequest {
+            temperature: Some(2.5),
+            ..request()
+        };
+        assert!(error_message(&temperature, Some(1)).starts_with("temperature"));
+
+        let seed = STTRequest {
+            seed: Some(MAX_SEED + 1),
+            ..request()
+        };
+        assert!(error_message(&seed, Some(1)).starts_with("seed"));
+
+        let pcm = STTRequest {
+            file_format: Some(FileFormat::PcmS16le16),
+            ..request()
+        };
+        assert!(error_message(&pcm, Some(3)).starts_with("file"));
+        assert!(validate_request(&pcm, &Model::ScribeV1, Some(4)).is_ok());
+
+        let formats = STTRequest {
+            additional_formats: Some(vec![
+                AdditionalFormat::Pdf(ExportOptions::new()),
+                AdditionalFormat::Pdf(ExportOptions::new().include_speakers(true)),
+            ]),
+            ..request()
+        };
+        assert!(error_message(&formats, Some(1)).starts_with("additional_formats"));
+
+        let webhook_id = STTRequest {
+            webhook_id: Some("hook".to_string()),
+            ..request()
+        };
+        assert!(error_message(&webhook_id, Some(1)).starts_with("webhook_id"));
+    }
+
+    #[test]
+    fn test_model_capabilities() {
+        let request = STTRequest {
+            language_code: Some(LanguageCode::parse("fr").unwrap()),
+            use_multi_channel: Some(true),
+            ..request()
+        };
+
+        for model in [
+            Model::ScribeV1,
+            Model::ScribeV1Experimental,
+            Model::ScribeV2,
+        ] {
+            let capabilities = model.capabilities();
+            assert!(capabilities.supports_language_enforcement);
+            assert!(capabilities.supports_multichannel);
+            assert!(validate_request(&request, &model, Some(1)).is_ok());
+        }
+        assert!(!Model::ScribeV1.capabilities().supports_keyterms);
+        assert!(Model::ScribeV2.capabilities().supports_keyterms);
+    }
+
+    #[test]
+    fn test_keyterms() {
+        let keyterms = |keyterms: Vec<&str>| STTRequest {
+            keyterms: Some(keyterms.into_iter().map(String::from).collect()),
+            ..request()
+        };
+
+        let valid = keyterms(vec!["ElevenLabs", "Scribe v2"]);
+        assert!(validate_request(&valid, &Model::ScribeV2, Some(1)).is_ok());
+        assert!(error_message(&valid, Some(1)).contains("does not support keyterm"));
+
+        let invalid_keyterms = [
+            keyterms(vec!["ok"; MAX_KEYTERMS + 1]),
+            keyterms(vec![" "]),
+            keyterms(vec![&"x".repeat(MAX_KEYTERM_LENGTH)]),
+            keyterms(vec!["one two three four five six"]),
+        ];
+        for request in invalid_keyterms {
+            match validate_request(&request, &Model::ScribeV2, Some(1)) {
+                Err(ElevenLabsSTTError::ValidationError(message)) => {
+                    assert!(message.starts_with("keyterms"))
+                }
+                other => panic!("expected a validation error, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_webhook_metadata() {
+        assert!(validate_webhook_metadata(r#"{"job": {"id": 1}}"#).is_ok());
+        assert!(validate_webhook_metadata(r#"{"job": {"id": {"n": 1}}}"#).is_err());
+        assert!(validate_webhook_metadata(r#"[1, 2]"#).is_err());
+        assert!(validate_webhook_metadata("not json").is_err());
+
+        let large = format!(r#"{{"blob": "{}"}}"#, "x".repeat(MAX_WEBHOOK_METADATA_SIZE));
+        assert!(validate_webhook_metadata(&large).is_err());
+    }
+}
@@ -0,0 +1,365 @@
+//! Receiving speech-to-text results delivered by ElevenLabs webhooks.
+//!
+//! Every delivery carries an `ElevenLabs-Signature` header of the form
+//! `t=<unix timestamp>,v0=<hex HMAC-SHA256>`, where the HMAC is computed with
+//! the webhook secret over `"<timestamp>.<raw body>"`.
+
+use crate::error::ElevenLabsSTTError;
+use crate::types::STTResponse;
+use hmac::{Hmac, Mac};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[cfg(feature = "axum")]
+pub mod axum;
+
+/// Name of the header carrying the webhook signature
+pub const SIGNATURE_HEADER: &str = "ElevenLabs-Signature";
+
+/// Default accepted age of a webhook delivery
+pub const DEFAULT_TOLERANCE: Duration = Duration::from_secs(30 * 60);
+
+/// Event type of a speech-to-text transcription delivery
+pub const TRANSCRIPTION_EVENT: &str = "speech_to_text_transcription";
+
+/// A verified webhook delivery
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    #[serde(rename = "type")]
+    pub type_field: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_timestamp: Option<i64>,
+    pub data: WebhookTranscription,
+}
+
+/// Transcription payload of a `speech_to_text_transcription` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookTranscription {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    pub transcription: STTResponse,
+    /// `webhook_metadata` sent with the request, echoed back as-is
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_metadata: Option<serde_json::Value>,
+}
+
+impl WebhookTranscription {
+    /// Decode the echoed `webhook_metadata` into a typed value.
+    /// Metadata echoed as a JSON string is parsed first.
+    pub fn metadata<T: DeserializeOwned>(&self) -> Result<Option<T>, ElevenLabsSTTError> {
+        let value = match &self.webhook_metadata {
+            None | Some(serde_json::Value::Null) => return Ok(None),
+            Some(serde_json::Value::String(raw)) => serde_json::from_str(raw),
+            Some(value) => T::deserialize(value),
+        };
+
+        value
+            .map(Some)
+            .map_err(|e| webhook_error(format!("invalid webhook_metadata: {}", e)))
+    }
+}
+
+fn webhook_error(message: String) -> ElevenLabsSTTError {
+    ElevenLabsSTTError::WebhookError(message)
+}
+
+/// Verifies webhook signatures and decodes their payloads
+#[derive(Clone)]
+pub struct WebhookVerifier {
+    secret: String,
+    tolerance: Duration,
+    seen_signatures: Option<Arc<Mutex<HashMap<String, i64>>>>,
+}
+
+impl WebhookVerifier {
+    /// Create a verifier for the webhook secret configured in ElevenLabs
+    pub fn new<S: Into<String>>(secret: S) -> Self {
+        Self {
+            secret: secret.into(),
+            tolerance: DEFAULT_TOLERANCE,
+            seen_signatures: None,
+        }
+    }
+
+    /// Set how old (or far in the future) a signature timestamp may be
+    pub fn tolerance(mut self, tolerance: Duration) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Reject a signature already accepted within the tolerance window.
+    /// Seen signatures are kept in memory and shared between clones of the verifier.
+    /// When handling a verified delivery fails, call [`WebhookVerifier::forget`]
+    /// so the redelivery is not rejected as a replay.
+    pub fn replay_protection(mut self, enabled: bool) -> Self {
+        self.seen_signatures = enabled.then(|| Arc::new(Mutex::new(HashMap::new())));
+        self
+    }
+
+    /// Verify the signature header against the raw request body
+    pub fn verify(&self, body: &[u8], signature_header: &str) -> Result<(), ElevenLabsSTTError> {
+        self.verify_at(body, signature_header, chrono::Utc::now().timestamp())
+    }
+
+    /// Verify the signature as if the current unix time were `now`
+    pub fn verify_at(
+        &self,
+        body: &[u8],
+        signature_header: &str,
+        now: i64,
+    ) -> Result<(), ElevenLabsSTTError> {
+        let (timestamp, signatures) = parse_signature_header(signature_header)?;
+
+        let age = now.abs_diff(timestamp);
+        if age > self.tolerance.as_secs() {
+            return Err(webhook_error(format!(
+                "signature timestamp is {}s away from now, outside the {}s tolerance",
+                age,
+                self.tolerance.as_secs()
+            )));
+        }
+
+        let matched = signatures
+            .iter()
+            .find(|signature| self.signature_matches(timestamp, body, signature))
+            .ok_or_else(|| webhook_error("signature does not match".to_string()))?;
+
+        if let Some(seen) = &self.seen_signatures {
+            let mut seen = seen.lock().unwrap_or_else(|e| e.into_inner());
+            let tolerance = self.tolerance.as_secs();
+            seen.retain(|_, seen_timestamp| now.abs_diff(*seen_timestamp) <= tolerance);
+
+            if seen.insert(matched.to_string(), timestamp).is_some() {
+                return Err(webhook_error("signature has already been used".to_string()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Forget the signatures of a header accepted by `verify`, so the same delivery
+    /// is accepted again. Does nothing without replay protection.
+    pub fn forget(&self, signature_header: &str) {
+        let (Some(seen), Ok((_, signatures))) = (
+            &self.seen_signatures,
+            parse_signature_header(signature_header),
+        ) else {
+            return;
+        };
+
+        let mut seen = seen.lock().unwrap_or_else(|e| e.into_inner());
+        for signature in signatures {
+            seen.remove(signature);
+        }
+    }
+
+    /// Verify the signature and decode the transcription event
+    pub fn construct_event(
+        &self,
+        body: &[u8],
+        signature_header: &str,
+    ) -> Result<WebhookEvent, ElevenLabsSTTError> {
+        self.verify(body, signature_header)?;
+        parse_event(body)
+    }
+
+    /// Verify the signature as of `now` and decode the transcription event
+    pub fn construct_event_at(
+        &self,
+        body: &[u8],
+        signature_header: &str,
+        now: i64,
+    ) -> Result<WebhookEvent, ElevenLabsSTTError> {
+        self.verify_at(body, signature_header, now)?;
+        parse_event(body)
+    }
+
+    /// Compute the `ElevenLabs-Signature` header for a body, useful for tests and fixtures
+    pub fn sign(&self, body: &[u8], timestamp: i64) -> String {
+        let signature = hex::encode(self.mac(timestamp, body).finalize().into_bytes());
+        format!("t={},v0={}", timestamp, signature)
+    }
+
+    fn mac(&self, timestamp: i64, body: &[u8]) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(timestamp.to_string().as_bytes());
+        mac.update(b".");
+        mac.update(body);
+        mac
+    }
+
+    fn signature_matches(&self, timestamp: i64, body: &[u8], signature: &str) -> bool {
+        let Ok(signature) = hex::decode(signature) else {
+            return false;
+        };
+
+        // Constant-time comparison
+        self.mac(timestamp, body).verify_slice(&signature).is_ok()
+    }
+}
+
+impl std::fmt::Debug for WebhookVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookVerifier")
+            .field("tolerance", &self.tolerance)
+            .field("replay_protection", &self.seen_signatures.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Split `t=<timestamp>,v0=<signature>[,v0=<signature>...]`
+fn parse_signature_header(header: &str) -> Result<(i64, Vec<&str>), ElevenLabsSTTError> {
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+
+    for item in header.split(',') {
+        match item.trim().split_once('=') {
+            Some(("t", value)) => {
+                timestamp = Some(value.parse::<i64>().map_err(|_| {
+                    webhook_error(format!("invalid signature timestamp '{}'", value))
+                })?);
+            }
+            Some(("v0", value)) => signatures.push(value),
+            _ => {}
+        }
+    }
+
+    let timestamp =
+        timestamp.ok_or_else(|| webhook_error("signature header has no timestamp".to_string()))?;
+    if signatures.is_empty() {
+        return Err(webhook_error(
+            "signature header has no v0 signature".to_string(),
+        ));
+    }
+
+    Ok((timestamp, signatures))
+}
+
+/// Decode a transcription event without checking its signature
+pub fn parse_event(body: &[u8]) -> Result<WebhookEvent, ElevenLabsSTTError> {
+    let event: WebhookEvent = serde_json::from_slice(body)
+        .map_err(|e| webhook_error(format!("invalid webhook payload: {}", e)))?;
+
+    if event.type_field != TRANSCRIPTION_EVENT {
+        return Err(webhook_error(format!(
+            "unexpected webhook event type '{}'",
+            event.type_field
+        )));
+    }
+
+    Ok(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "wsec_test";
+    const NOW: i64 = 1_739_537_297;
+    const PAYLOAD: &str = r#"{
+        "type": "speech_to_text_transcription",
+        "event_timestamp": 1739537297,
+        "data": {
+            "request_id": "req_1",
+            "transcription": {
+                "language_code": "eng",
+                "language_probability": 0.98,
+                "text": "Hello world",
+                "words": [{"text": "Hello", "start": 0.0, "end": 0.4, "type": "word"}]
+            },
+            "webhook_metadata": {"job_id": 42}
+        }
+    }"#;
+
+    #[derive(Deserialize)]
+    struct Metadata {
+        job_id: u32,
+    }
+
+    #[test]
+    fn test_construct_event() {
+        let verifier = WebhookVerifier::new(SECRET);
+        let header = verifier.sign(PAYLOAD.as_bytes(), NOW);
+
+        let event = verifier
+            .construct_event_at(PAYLOAD.as_bytes(), &header, NOW + 60)
+            .unwrap();
+
+        assert_eq!(event.data.request_id.as_deref(), Some("req_1"));
+        assert_eq!(
+            event.data.transcription.text.as_deref(),
+            Some("Hello world")
+        );
+        let metadata: Metadata = event.data.metadata().unwrap().unwrap();
+        assert_eq!(metadata.job_id, 42);
+    }
+
+    #[test]
+    fn test_accepts_unknown_language_codes() {
+        let verifier = WebhookVerifier::new(SECRET);
+
+        for (code, expected) in [("zh-Hans", Some("zh-Hans")), ("xx", Some("xx")), ("", None)] {
+            let payload = PAYLOAD.replace(r#""eng""#, &format!("{:?}", code));
+            let header = verifier.sign(payload.as_bytes(), NOW);
+
+            let event = verifier
+                .construct_event_at(payload.as_bytes(), &header, NOW)
+                .unwrap();
+            let language_code = event.data.transcription.language_code;
+            assert_eq!(language_code.as_ref().map(|c| c.iso639_3()), expected);
+        }
+    }
+
+    #[test]
+    fn test_rejects_bad_signatures() {
+        let verifier = WebhookVerifier::new(SECRET);
+        let header = verifier.sign(PAYLOAD.as_bytes(), NOW);
+
+        let tampered = PAYLOAD.replace("Hello world", "Goodbye");
+        assert!(
+            verifier
+                .verify_at(tampered.as_bytes(), &header, NOW)
+                .is_err()
+        );
+
+        let other = WebhookVerifier::new("wsec_other");
+        assert!(other.verify_at(PAYLOAD.as_bytes(), &header, NOW).is_err());
+
+        let expired = NOW + DEFAULT_TOLERANCE.as_secs() as i64 + 1;
+        assert!(
+            verifier
+                .verify_at(PAYLOAD.as_bytes(), &header, expired)
+                .is_err()
+        );
+
+        assert!(
+            verifier
+                .verify_at(PAYLOAD.as_bytes(), "v0=abc", NOW)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_replay_protection() {
+        let verifier = WebhookVerifier::new(SECRET).replay_protection(true);
+        let header = verifier.sign(PAYLOAD.as_bytes(), NOW);
+
+        assert!(verifier.verify_at(PAYLOAD.as_bytes(), &header, NOW).is_ok());
+        assert!(matches!(
+            verifier.verify_at(PAYLOAD.as_bytes(), &header, NOW + 1),
+            Err(ElevenLabsSTTError::WebhookError(_))
+        ));
+
+        verifier.forget(&header);
+        assert!(
+            verifier
+                .verify_at(PAYLOAD.as_bytes(), &header, NOW + 2)
+                .is_ok()
+        );
+    }
+}
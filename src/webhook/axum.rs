@@ -0,0 +1,250 @@
+//! Ready-made [axum](https://docs.rs/axum) handler for receiving speech-to-text webhooks.
+//!
+//! ```rust,no_run
+//! use axum::Router;
+//! use elevenlabs_stt::WebhookVerifier;
+//! use elevenlabs_stt::webhook::axum::webhook_handler;
+//!
+//! # async fn run() {
+//! let verifier = WebhookVerifier::new("your-webhook-secret");
+//! let app: Router = Router::new().route(
+//!     "/webhooks/elevenlabs",
+//!     webhook_handler(verifier, |event| async move {
+//!         println!("Transcription: {:?}", event.data.transcription.text);
+//!         Ok::<(), String>(())
+//!     }),
+//! );
+//! # }
+//! ```
+
+use super::{SIGNATURE_HEADER, WebhookEvent, WebhookVerifier, parse_event};
+use axum::body::Bytes;
+use axum::extract::DefaultBodyLimit;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{MethodRouter, post};
+use std::future::Future;
+
+/// Largest webhook body accepted by [`webhook_handler`] (64MB). Transcripts of long
+/// recordings with word timestamps easily exceed axum's default limit of 2MB.
+pub const DEFAULT_BODY_LIMIT: usize = 64 * 1024 * 1024;
+
+/// Build a `POST` route that verifies the signature, decodes the transcription
+/// and hands it to `callback`.
+///
+/// Responds with `200` once the callback succeeds, `401` for a missing or invalid
+/// signature, `400` for an undecodable payload and `500` if the callback fails,
+/// so ElevenLabs retries the delivery. With replay protection, the signature of a
+/// failed delivery is forgotten so the retry is accepted. The callback error is not
+/// sent back to the caller; log it in the callback if needed.
+/// Bodies larger than [`DEFAULT_BODY_LIMIT`] are rejected with `413`.
+pub fn webhook_handler<S, F, Fut, E>(verifier: WebhookVerifier, callback: F) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+    F: Fn(WebhookEvent) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), E>> + Send + 'static,
+    E: 'static,
+{
+    webhook_handler_with_body_limit(verifier, DEFAULT_BODY_LIMIT, callback)
+}
+
+/// Like [`webhook_handler`], rejecting bodies larger than `body_limit` bytes
+pub fn webhook_handler_with_body_limit<S, F, Fut, E>(
+    verifier: WebhookVerifier,
+    body_limit: usize,
+    callback: F,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+    F: Fn(WebhookEvent) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), E>> + Send + 'static,
+    E: 'static,
+{
+    post(move |headers: HeaderMap, body: Bytes| async move {
+        handle_webhook(&verifier, &headers, &body, callback).await
+    })
+    .layer(DefaultBodyLimit::max(body_limit))
+}
+
+async fn handle_webhook<F, Fut, E>(
+    verifier: &WebhookVerifier,
+    headers: &HeaderMap,
+    body: &[u8],
+    callback: F,
+) -> (StatusCode, String)
+where
+    F: Fn(WebhookEvent) -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+{
+    let Some(signature) = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return (
+            StatusCode::UNAUTHORIZED,
+            format!("missing {} header", SIGNATURE_HEADER),
+        );
+    };
+
+    if let Err(e) = verifier.verify(body, signature) {
+        return (StatusCode::UNAUTHORIZED, e.to_string());
+    }
+
+    let event = match parse_event(body) {
+        Ok(event) => event,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()),
+    };
+
+    match callback(event).await {
+        Ok(()) => (StatusCode::OK, String::new()),
+        Err(_) => {
+            verifier.forget(signature);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "callback failed".to_string(),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::body::Body;
+    use axum::http::Request;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::ServiceExt;
+
+    const PAYLOAD: &str =
+        r#"{"type":"speech_to_text_transcription","data":{"transcription":{"text":"Hi"}}}"#;
+
+    fn app(calls: Arc<AtomicUsize>, fail: bool) -> Router {
+        Router::new().route(
+            "/hook",
+            webhook_handler(WebhookVerifier::new("secret"), move |event| {
+                let calls = calls.clone();
+                async move {
+                    assert_eq!(event.data.transcription.text.as_deref(), Some("Hi"));
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    if fail { Err("storage down") } else { Ok(()) }
+                }
+            }),
+        )
+    }
+
+    /// App with replay protection whose callback fails on its first call only
+    fn flaky_app(calls: Arc<AtomicUsize>) -> Router {
+        let verifier = WebhookVerifier::new("secret").replay_protection(true);
+        Router::new().route(
+            "/hook",
+            webhook_handler(verifier, move |_| {
+                let calls = calls.clone();
+                async move {
+                    match calls.fetch_add(1, Ordering::SeqCst) {
+                        0 => Err("storage down"),
+                        _ => Ok(()),
+                    }
+                }
+            }),
+        )
+    }
+
+    async fn post_webhook(app: Router, body: &str, signature: Option<String>) -> StatusCode {
+        let mut request = Request::builder().method("POST").uri("/hook");
+        if let Some(signature) = signature {
+            request = request.header(SIGNATURE_HEADER, signature);
+        }
+
+        app.oneshot(request.body(Body::from(body.to_string())).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    fn sign(body: &str) -> String {
+        WebhookVerifier::new("secret").sign(body.as_bytes(), chrono::Utc::now().timestamp())
+    }
+
+    #[tokio::test]
+    async fn test_webhook_handler_status_codes() {
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let ok = post_webhook(app(calls.clone(), false), PAYLOAD, Some(sign(PAYLOAD))).await;
+        assert_eq!(ok, StatusCode::OK);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let missing = post_webhook(app(calls.clone(), false), PAYLOAD, None).await;
+        assert_eq!(missing, StatusCode::UNAUTHORIZED);
+
+        let forged = post_webhook(app(calls.clone(), false), PAYLOAD, Some(sign("other"))).await;
+        assert_eq!(forged, StatusCode::UNAUTHORIZED);
+
+        let invalid = post_webhook(app(calls.clone(), false), "{}", Some(sign("{}"))).await;
+        assert_eq!(invalid, StatusCode::BAD_REQUEST);
+
+        let failed = post_webhook(app(calls.clone(), true), PAYLOAD, Some(sign(PAYLOAD))).await;
+        assert_eq!(failed, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_handler_hides_callback_errors() {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/hook")
+            .header(SIGNATURE_HEADER, sign(PAYLOAD))
+            .body(Body::from(PAYLOAD))
+            .unwrap();
+
+        let response = app(Arc::new(AtomicUsize::new(0)), true)
+            .oneshot(request)
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, "callback failed");
+    }
+
+    #[tokio::test]
+    async fn test_webhook_handler_accepts_retry_after_failure() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = flaky_app(calls.clone());
+        let signature = sign(PAYLOAD);
+
+        let failed = post_webhook(app.clone(), PAYLOAD, Some(signature.clone())).await;
+        assert_eq!(failed, StatusCode::INTERNAL_SERVER_ERROR);
+
+        let retried = post_webhook(app.clone(), PAYLOAD, Some(signature.clone())).await;
+        assert_eq!(retried, StatusCode::OK);
+
+        let replayed = post_webhook(app, PAYLOAD, Some(signature)).await;
+        assert_eq!(replayed, StatusCode::UNAUTHORIZED);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_handler_accepts_large_transcripts() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let words = r#"{"text":"Hi","start":0.0,"end":0.1,"type":"word"},"#.repeat(60_000);
+        let payload = format!(
+            r#"{{"type":"speech_to_text_transcription","data":{{"transcription":{{"text":"Hi","words":[{}]}}}}}}"#,
+            words.trim_end_matches(',')
+        );
+        assert!(payload.len() > 2 * 1024 * 1024);
+
+        let status = post_webhook(app(calls.clone(), false), &payload, Some(sign(&payload))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let limited = Router::new().route(
+            "/hook",
+            webhook_handler_with_body_limit(WebhookVerifier::new("secret"), 1024, |_| async {
+                Ok::<(), String>(())
+            }),
+        );
+        let status = post_webhook(limited, &payload, Some(sign(&payload))).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
@@ -1,4 +1,11 @@
-use elevenlabs_stt::{ElevenLabsSTTClient, ElevenLabsSTTError, models};
+use elevenlabs_stt::{ElevenLabsSTTClient, ElevenLabsSTTError, RetryPolicy, models};
+use wiremock::MockServer;
+
+/// Client talking to `server`, without retries so each mock sees exactly the requests sent
+fn mock_client(server: &MockServer) -> ElevenLabsSTTClient {
+    ElevenLabsSTTClient::with_base_url("test-key".to_string(), server.uri())
+        .with_retry_policy(RetryPolicy::none())
+}
 
 #[tokio::test]
 async fn test_client_creation() {
@@ -46,3 +53,878 @@ mod mock_tests {
         assert_eq!(true, true);
     }
 }
+
+mod transcript_tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_get_transcript() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/speech-to-text/transcripts/tr%201"))
+            .and(header("xi-api-key", "test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "language_code": "eng",
+                "text": "Hello world",
+                "transcription_id": "tr 1"
+            })))
+            .mount(&server)
+            .await;
+
+        let transcript = mock_client(&server).get_transcript("tr 1").await.unwrap();
+
+        assert_eq!(transcript.text.as_deref(), Some("Hello world"));
+        assert_eq!(transcript.transcription_id.as_deref(), Some("tr 1"));
+    }
+
+    #[tokio::test]
+    async fn test_delete_transcript() {
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/speech-to-text/transcripts/tr_1"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        mock_client(&server)
+            .delete_transcript("tr_1")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_transcript_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+                "detail": {"status": "invalid_api_key", "message": "Invalid API key"}
+            })))
+            .mount(&server)
+            .await;
+
+        let result = mock_client(&server).get_transcript("tr_1").await;
+        assert!(matches!(
+            result,
+            Err(ElevenLabsSTTError::AuthenticationError(_))
+        ));
+
+        let result = mock_client(&server).get_transcript("").await;
+        assert!(matches!(
+            result,
+            Err(ElevenLabsSTTError::ValidationError(_))
+        ));
+    }
+}
+
+mod poll_tests {
+    use super::*;
+    use elevenlabs_stt::{CancellationToken, PollOptions};
+    use std::time::Duration;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    fn options() -> PollOptions {
+        PollOptions::new()
+            .initial_interval(Duration::from_millis(10))
+            .max_interval(Duration::from_millis(20))
+    }
+
+    fn not_found() -> ResponseTemplate {
+        ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "detail": {"status": "transcript_not_found", "message": "Transcript not found"}
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_transcript() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/speech-to-text/transcripts/tr_1"))
+            .respond_with(not_found())
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/speech-to-text/transcripts/tr_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "text": "Done",
+                "transcription_id": "tr_1"
+            })))
+            .mount(&server)
+            .await;
+
+        let transcript = mock_client(&server)
+            .wait_for_transcript("tr_1", options())
+            .await
+            .unwrap();
+
+        assert_eq!(transcript.text.as_deref(), Some("Done"));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_transcript_timeout_and_cancel() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(not_found())
+            .mount(&server)
+            .await;
+
+        let result = mock_client(&server)
+            .wait_for_transcript("tr_1", options().timeout(Duration::from_millis(100)))
+            .await;
+        assert!(matches!(result, Err(ElevenLabsSTTError::Timeout(_))));
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let result = mock_client(&server)
+            .wait_for_transcript("tr_1", options().cancellation_token(token))
+            .await;
+        assert!(matches!(result, Err(ElevenLabsSTTError::Cancelled)));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_transcript_fails_fast_on_foreign_404() {
+        // A wrong base URL answers 404 without the API error body
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("Not Found"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = mock_client(&server)
+            .wait_for_transcript("tr_1", options())
+            .await;
+        assert!(matches!(
+            result,
+            Err(ElevenLabsSTTError::ApiError { status: 404, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_transcript_fails_fast_on_unknown_route() {
+        // The framework's own 404 for a route the API does not have, e.g. without `/v1`
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(404)
+                    .set_body_json(serde_json::json!({"detail": "Not Found"})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = mock_client(&server)
+            .wait_for_transcript("tr_1", options())
+            .await;
+        assert!(matches!(
+            result,
+            Err(ElevenLabsSTTError::ApiError { status: 404, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_transcript_returns_other_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(401).set_body_string("Invalid API key"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = mock_client(&server)
+            .wait_for_transcript("tr_1", options())
+            .await;
+        assert!(matches!(
+            result,
+            Err(ElevenLabsSTTError::AuthenticationError(_))
+        ));
+    }
+}
+
+mod alignment_tests {
+    use super::*;
+    use wiremock::matchers::{body_string_contains, header, method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_forced_alignment() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/forced-alignment"))
+            .and(header("xi-api-key", "test-key"))
+            .and(body_string_contains("Hello world"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "characters": [{"text": "H", "start": 0.0, "end": 0.1}],
+                "words": [
+                    {"text": "Hello", "start": 0.0, "end": 0.4, "loss": 0.1},
+                    {"text": "world", "start": 0.5, "end": 0.9, "loss": 0.3}
+                ],
+                "loss": 0.2
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let alignment = client
+            .forced_alignment(vec![0u8; 16], "Hello world")
+            .execute()
+            .await
+            .unwrap();
+
+        assert_eq!(alignment.words.len(), 2);
+        assert_eq!(alignment.words[1].text, "world");
+        assert_eq!(alignment.loss, Some(0.2));
+    }
+
+    #[tokio::test]
+    async fn test_forced_alignment_cancelled() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/forced-alignment"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"words": []}))
+                    .set_delay(std::time::Duration::from_secs(5)),
+            )
+            .mount(&server)
+            .await;
+
+        let token = elevenlabs_stt::CancellationToken::new();
+        let cancel = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            cancel.cancel();
+        });
+
+        let client = mock_client(&server);
+        let result = client
+            .forced_alignment(vec![0u8; 16], "Hello world")
+            .cancellation_token(token)
+            .execute()
+            .await;
+
+        assert!(matches!(result, Err(ElevenLabsSTTError::Cancelled)));
+    }
+}
+
+mod subscription_tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_subscription() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/user/subscription"))
+            .and(header("xi-api-key", "test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "tier": "pro",
+                "character_count": 400,
+                "character_limit": 1000,
+                "next_character_count_reset_unix": 1738356858
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let subscription = client.subscription().await.unwrap();
+
+        assert_eq!(subscription.tier.as_deref(), Some("pro"));
+        assert_eq!(subscription.remaining_credits(), Some(600));
+    }
+}
+
+mod keyterm_tests {
+    use super::*;
+    use elevenlabs_stt::Model;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_keyterms_are_repeated_fields() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/speech-to-text"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "text": "ElevenLabs Scribe"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        client
+            .speech_to_text(vec![0u8; 16])
+            .model(Model::ScribeV2)
+            .keyterms(["ElevenLabs", "Scribe"])
+            .execute()
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert_eq!(body.matches("name=\"keyterms\"").count(), 2);
+        assert!(body.contains("ElevenLabs") && body.contains("Scribe"));
+    }
+}
+
+mod pcm_tests {
+    use super::*;
+    use elevenlabs_stt::FileFormat;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_pcm_upload_is_not_sniffed() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/speech-to-text"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "text": "Hello"
+            })))
+            .mount(&server)
+            .await;
+
+        // Samples that happen to start like an MP3 frame
+        let mut samples = std::fs::read("inputs/speech.mp3").unwrap();
+        samples.truncate(4096);
+
+        let client = mock_client(&server);
+        client
+            .speech_to_text(samples)
+            .file_format(FileFormat::PcmS16le16)
+            .execute()
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(body.contains("name=\"file\"; filename=\"file\""));
+        assert!(body.contains("Content-Type: application/octet-stream"));
+        assert!(!body.contains("audio/mpeg"));
+    }
+}
+
+mod additional_format_tests {
+    use super::*;
+    use elevenlabs_stt::{AdditionalFormat, ExportOptions, TextExportOptions};
+    use wiremock::matchers::{body_string_contains, method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_additional_formats() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/speech-to-text"))
+            .and(body_string_contains(
+                r#"[{"format":"srt","max_characters_per_line":32},{"format":"docx"}]"#,
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "text": "Hello",
+                "additional_formats": [
+                    {"requested_format": "srt", "file_extension": "srt",
+                     "content_type": "text/srt", "is_base64_encoded": false,
+                     "content": "1\n00:00:00,000 --> 00:00:00,400\nHello\n"},
+                    {"requested_format": "docx", "file_extension": "docx",
+                     "content_type": "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+                     "is_base64_encoded": true, "content": "UEsDBA=="}
+                ]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let response = client
+            .speech_to_text(vec![0u8; 16])
+            .additional_format(AdditionalFormat::Srt(
+                TextExportOptions::new().max_characters_per_line(32),
+            ))
+            .additional_format(AdditionalFormat::Docx(ExportOptions::new()))
+            .execute()
+            .await
+            .unwrap();
+
+        let srt = response.additional_format("srt").unwrap();
+        assert!(srt.text().unwrap().contains("Hello"));
+        let docx = response.additional_format("docx").unwrap();
+        assert_eq!(docx.content, b"PK\x03\x04");
+    }
+}
+
+mod retry_tests {
+    use super::*;
+    use std::time::Duration;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    fn slow_client(server: &MockServer, retry_policy: RetryPolicy) -> ElevenLabsSTTClient {
+        ElevenLabsSTTClient::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .timeout(Duration::from_millis(100))
+            .retry_policy(retry_policy.initial_backoff(Duration::from_millis(1)))
+            .build()
+            .unwrap()
+    }
+
+    fn slow_response() -> ResponseTemplate {
+        ResponseTemplate::new(200)
+            .set_body_json(serde_json::json!({"text": "late"}))
+            .set_delay(Duration::from_millis(500))
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_upload_is_not_resent() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/speech-to-text"))
+            .respond_with(slow_response())
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = slow_client(&server, RetryPolicy::new())
+            .speech_to_text(vec![0u8; 16])
+            .execute()
+            .await;
+
+        match result {
+            Err(error) => assert!(error.may_have_been_processed()),
+            Ok(_) => panic!("expected a timeout"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeouts_are_retried_when_allowed() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/speech-to-text"))
+            .respond_with(slow_response())
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(slow_response())
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let policy = RetryPolicy::new().max_attempts(2);
+        let client = slow_client(&server, policy.clone().retry_uncertain_uploads(true));
+        assert!(
+            client
+                .speech_to_text(vec![0u8; 16])
+                .execute()
+                .await
+                .is_err()
+        );
+
+        // Reads are always safe to repeat
+        let client = slow_client(&server, policy);
+        assert!(client.get_transcript("tr_1").await.is_err());
+    }
+}
+
+mod stt_error_tests {
+    use super::*;
+    use std::time::Duration;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    async fn transcribe(server: &MockServer) -> Result<(), ElevenLabsSTTError> {
+        mock_client(server)
+            .speech_to_text(vec![0u8; 16])
+            .execute()
+            .await
+            .map(|_| ())
+    }
+
+    #[tokio::test]
+    async fn test_authentication_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/speech-to-text"))
+            .and(header("xi-api-key", "test-key"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+                "detail": {"status": "invalid_api_key", "message": "Invalid API key"}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        match transcribe(&server).await {
+            Err(ElevenLabsSTTError::AuthenticationError(message)) => {
+                assert_eq!(message, "Invalid API key")
+            }
+            other => panic!("expected an authentication error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_quota_exceeded_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/speech-to-text"))
+            .respond_with(ResponseTemplate::new(402).set_body_json(serde_json::json!({
+                "detail": {"status": "payment_required", "message": "Not enough credits"}
+            })))
+            .mount(&server)
+            .await;
+
+        match transcribe(&server).await {
+            Err(ElevenLabsSTTError::QuotaExceededError(message)) => {
+                assert_eq!(message, "Not enough credits")
+            }
+            other => panic!("expected a quota error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/speech-to-text"))
+            .respond_with(
+                ResponseTemplate::new(429)
+                    .insert_header("Retry-After", "7")
+                    .set_body_json(serde_json::json!({
+                        "detail": {"status": "too_many_concurrent_requests", "message": "Slow down"}
+                    })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let error = transcribe(&server).await.unwrap_err();
+        assert!(matches!(
+            error,
+            ElevenLabsSTTError::RateLimitError {
+                retry_after: Some(7),
+                ..
+            }
+        ));
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(7)));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_is_retried_after_delay() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/speech-to-text"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/speech-to-text"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "text": "Hello"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ElevenLabsSTTClient::with_base_url("test-key".to_string(), server.uri());
+        let response = client
+            .speech_to_text(vec![0u8; 16])
+            .execute()
+            .await
+            .unwrap();
+
+        assert_eq!(response.text.as_deref(), Some("Hello"));
+    }
+}
+
+mod streaming_tests {
+    use super::*;
+    use std::time::Duration;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack
+            .windows(needle.len())
+            .any(|window| window == needle)
+    }
+
+    fn retrying_client(server: &MockServer) -> ElevenLabsSTTClient {
+        mock_client(server).with_retry_policy(
+            RetryPolicy::new()
+                .max_attempts(2)
+                .initial_backoff(Duration::from_millis(1)),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_file_is_streamed_again_on_retry() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/speech-to-text"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/speech-to-text"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "text": "Hello"
+            })))
+            .mount(&server)
+            .await;
+
+        let response = retrying_client(&server)
+            .speech_to_text_file("inputs/speech.mp3")
+            .execute()
+            .await
+            .unwrap();
+        assert_eq!(response.text.as_deref(), Some("Hello"));
+
+        let audio = std::fs::read("inputs/speech.mp3").unwrap();
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        for request in &requests {
+            assert!(contains(&request.body, &audio));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reader_is_sent_once() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/speech-to-text"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let audio = std::fs::read("inputs/speech.mp3").unwrap();
+        let length = audio.len() as u64;
+        let result = retrying_client(&server)
+            .speech_to_text_reader(std::io::Cursor::new(audio), length)
+            .execute()
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ElevenLabsSTTError::ApiError { status: 503, .. })
+        ));
+    }
+}
+
+mod progress_tests {
+    use super::*;
+    use elevenlabs_stt::{CancellationToken, UploadProgress};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_progress_is_reported() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/speech-to-text"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "text": "Hello"
+            })))
+            .mount(&server)
+            .await;
+
+        let total = std::fs::metadata("inputs/speech.mp3").unwrap().len();
+        let updates = Arc::new(Mutex::new(Vec::<UploadProgress>::new()));
+        let recorded = updates.clone();
+        let (sender, receiver) = tokio::sync::watch::channel(UploadProgress::default());
+
+        let client = mock_client(&server);
+        client
+            .speech_to_text_file("inputs/speech.mp3")
+            .on_progress(move |progress| recorded.lock().unwrap().push(progress))
+            .execute()
+            .await
+            .unwrap();
+        client
+            .speech_to_text_file("inputs/speech.mp3")
+            .progress_watch(sender)
+            .execute()
+            .await
+            .unwrap();
+
+        let updates = updates.lock().unwrap();
+        assert!(!updates.is_empty());
+        assert!(
+            updates
+                .windows(2)
+                .all(|pair| pair[0].bytes_sent <= pair[1].bytes_sent)
+        );
+        let last = updates.last().unwrap();
+        assert_eq!((last.bytes_sent, last.total_bytes), (total, total));
+        assert_eq!(receiver.borrow().bytes_sent, total);
+    }
+
+    #[tokio::test]
+    async fn test_upload_is_cancelled() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/speech-to-text"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"text": "Hello"}))
+                    .set_delay(Duration::from_secs(5)),
+            )
+            .mount(&server)
+            .await;
+
+        let token = CancellationToken::new();
+        let cancel = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            cancel.cancel();
+        });
+
+        let result = mock_client(&server)
+            .speech_to_text_file("inputs/speech.mp3")
+            .cancellation_token(token)
+            .execute()
+            .await;
+
+        assert!(matches!(result, Err(ElevenLabsSTTError::Cancelled)));
+    }
+}
+
+mod submit_tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_submit_returns_acknowledgment() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/speech-to-text"))
+            .respond_with(ResponseTemplate::new(202).set_body_json(serde_json::json!({
+                "message": "Request accepted",
+                "request_id": "req_1",
+                "transcription_id": "tr_1"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let acknowledgment = mock_client(&server)
+            .speech_to_text(vec![0u8; 16])
+            .webhook_metadata(r#"{"job_id": 42}"#)
+            .submit()
+            .await
+            .unwrap();
+
+        assert_eq!(acknowledgment.request_id.as_deref(), Some("req_1"));
+        assert_eq!(acknowledgment.transcription_id.as_deref(), Some("tr_1"));
+
+        let requests = server.received_requests().await.unwrap();
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(body.contains("name=\"webhook\"\r\n\r\ntrue"));
+        assert!(body.contains(r#"{"job_id": 42}"#));
+    }
+
+    #[tokio::test]
+    async fn test_execute_rejects_webhook_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let result = mock_client(&server)
+            .speech_to_text(vec![0u8; 16])
+            .webhook(true)
+            .execute()
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ElevenLabsSTTError::ValidationError(_))
+        ));
+    }
+}
+
+mod sniffing_tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    async fn file_part_headers(server: &MockServer) -> Vec<String> {
+        server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| {
+                let body = String::from_utf8_lossy(&request.body);
+                let start = body.find("name=\"file\"").unwrap();
+                let end = start + body[start..].find("\r\n\r\n").unwrap();
+                body[start..end].to_string()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_file_part_is_named_and_typed() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/speech-to-text"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "text": "Hello"
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let audio = std::fs::read("inputs/speech.mp3").unwrap();
+        client.speech_to_text(audio).execute().await.unwrap();
+        client
+            .speech_to_text_file("inputs/speech.mp3")
+            .execute()
+            .await
+            .unwrap();
+
+        let headers = file_part_headers(&server).await;
+        assert_eq!(
+            headers[0],
+            "name=\"file\"; filename=\"file.mp3\"\r\nContent-Type: audio/mpeg"
+        );
+        assert_eq!(
+            headers[1],
+            "name=\"file\"; filename=\"speech.mp3\"\r\nContent-Type: audio/mpeg"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_non_media_is_not_uploaded() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let result = mock_client(&server)
+            .speech_to_text(b"%PDF-1.7 not audio".to_vec())
+            .execute()
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ElevenLabsSTTError::ValidationError(_))
+        ));
+    }
+}